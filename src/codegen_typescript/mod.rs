@@ -1,7 +1,9 @@
 use crate::mcp_server::{
-    MCPServer, MCPTool, MCPToolProperty, MCPToolPropertyRequired, MCPToolPropertyType, Value,
-    ValueSource,
+    Body, BodyEncoding, MCPServer, MCPTool, MCPToolProperty, MCPToolPropertyRequired,
+    MCPToolPropertyType, ParameterStyle, ParameterValue, Value, ValueSource,
 };
+use crate::template::server_url_code;
+use http::Method;
 use std::{collections::HashSet, fmt::Write};
 
 #[derive(Debug, Clone)]
@@ -42,16 +44,74 @@ fn tool_to_code(mcp_server: &MCPServer, tool: &MCPTool) -> anyhow::Result<String
         output,
         "import {{ RequestHandlerExtra }} from \"@modelcontextprotocol/sdk/shared/protocol.js\";"
     )?;
-    writeln!(
-        output,
-        "import {{ httpClient }} from \"../../../../http_client\";"
-    )?;
+    // Operations served by another server are sent directly, the HTTP client only calls
+    // `API_BASE_URL`
+    let server_url = tool
+        .call
+        .base_url
+        .as_ref()
+        .map(|url| server_url_code(url, &mcp_server.base_url_variables));
+    match &server_url {
+        None => writeln!(
+            output,
+            "import {{ httpClient }} from \"../../../../http_client\";"
+        )?,
+        Some(url) if url.starts_with('`') => writeln!(
+            output,
+            "import {{ API_SERVER_VARIABLES }} from \"../../../../constants\";"
+        )?,
+        Some(_) => {}
+    }
+
+    // Parameters that `toString()` can't serialize are serialized into the path by the tool
+    let styled_path_params: Vec<_> = tool
+        .call
+        .path_params
+        .iter()
+        .filter(|(_, parameter)| !is_plain(tool, parameter))
+        .collect();
+    let styled_query = tool
+        .call
+        .query
+        .values()
+        .any(|parameter| !is_plain(tool, parameter));
+
+    // Helpers to serialize parameters and encode the body, only the ones the tool needs
+    let mut helpers = match tool.call.body.as_ref().map(|body| body.encoding) {
+        Some(BodyEncoding::FormUrlEncoded) => vec![FORM_URL_ENCODED_HELPER],
+        Some(BodyEncoding::Multipart) => vec![FILE_HELPERS, MULTIPART_HELPER],
+        Some(BodyEncoding::Binary) => vec![FILE_HELPERS],
+        _ => vec![],
+    };
+    if !styled_path_params.is_empty() {
+        helpers.push(PATH_PARAMETER_HELPER);
+    }
+    if styled_query {
+        helpers.push(QUERY_HELPER);
+    }
+    if server_url.is_some() {
+        helpers.push(SERVER_CALL_HELPER);
+    }
+    for helper in &helpers {
+        write!(output, "{helper}")?;
+    }
+    if !helpers.is_empty() {
+        writeln!(output)?;
+    }
 
     // Generate Zod schema from tool input schema
-    let zod_schema = generate_zod_schema_from_tool(&tool)?;
+    let zod_schema = generate_zod_schema_from_tool(tool)?;
 
     // Generate setupTool function
     writeln!(output, "export function setupTool(server: McpServer) {{")?;
+    // Recursive schemas refer to themselves, so they are declared lazily and typed explicitly
+    for (name, definition) in &tool.definitions {
+        writeln!(
+            output,
+            "  const {name}: z.ZodTypeAny = z.lazy(() => {});",
+            mcp_tool_property_to_zod_type(definition, 1)?
+        )?;
+    }
     writeln!(output, "  const params = {zod_schema};")?;
     writeln!(
         output,
@@ -68,9 +128,43 @@ fn tool_to_code(mcp_server: &MCPServer, tool: &MCPTool) -> anyhow::Result<String
 
     // Generate API call logic
     writeln!(output, "      try {{")?;
-    writeln!(output, "        const response = await httpClient.call({{")?;
+    match &server_url {
+        Some(url) => writeln!(
+            output,
+            "        const response = await callServer({url}, {{"
+        )?,
+        None => writeln!(output, "        const response = await httpClient.call({{")?,
+    }
 
-    writeln!(output, "          path: `{}`,", tool.call.path)?;
+    let mut path = tool.call.path.clone();
+    for (key, parameter) in &styled_path_params {
+        path = path.replace(
+            &format!("{{{key}}}"),
+            &format!(
+                "${{serializePathParameter(\"{key}\", {}, \"{}\", {})}}",
+                raw_value(&parameter.value),
+                parameter.style.as_str(),
+                parameter.explode
+            ),
+        );
+    }
+    if styled_query {
+        let parameters: Vec<_> = tool
+            .call
+            .query
+            .iter()
+            .map(|(key, parameter)| {
+                format!(
+                    "[\"{key}\", {}, \"{}\", {}]",
+                    raw_value(&parameter.value),
+                    parameter.style.as_str(),
+                    parameter.explode
+                )
+            })
+            .collect();
+        path.push_str(&format!("${{serializeQuery([{}])}}", parameters.join(", ")));
+    }
+    writeln!(output, "          path: `{path}`,")?;
     writeln!(output, "          method: '{}',", tool.call.method)?;
 
     if let Some(_oauth2_info) = &mcp_server.oauth2_info {
@@ -83,48 +177,81 @@ fn tool_to_code(mcp_server: &MCPServer, tool: &MCPTool) -> anyhow::Result<String
     fn display_value(value: &ValueSource) -> String {
         match value {
             ValueSource::Fixed(value) => match value {
-                Value::Boolean(_) | Value::Number(_) => format!("{value}.toString()"),
+                Value::Boolean(_) | Value::Number(_) => format!("\"{value}\""),
                 Value::String(value) => format!("\"{value}\""),
             },
             ValueSource::Property(property) => format!("args.{property}?.toString()"),
         }
     }
 
-    if !tool.call.path_params.is_empty() {
+    if tool.call.path_params.len() > styled_path_params.len() {
         writeln!(output, "          pathParams: {{")?;
-        for (key, value) in &tool.call.path_params {
-            writeln!(output, "            \"{key}\": {},", display_value(value))?;
+        for (key, parameter) in &tool.call.path_params {
+            if is_plain(tool, parameter) {
+                writeln!(
+                    output,
+                    "            \"{key}\": {},",
+                    display_value(&parameter.value)
+                )?;
+            }
         }
         writeln!(output, "          }},")?;
     }
 
-    if !tool.call.query.is_empty() {
+    if !tool.call.query.is_empty() && !styled_query {
         writeln!(output, "          query: {{")?;
-        for (key, value) in &tool.call.query {
-            writeln!(output, "            \"{key}\": {},", display_value(value))?;
+        for (key, parameter) in &tool.call.query {
+            writeln!(
+                output,
+                "            \"{key}\": {},",
+                display_value(&parameter.value)
+            )?;
         }
         writeln!(output, "          }},")?;
     }
 
-    if !tool.call.headers.is_empty() {
+    if !tool.call.headers.is_empty() || !tool.call.cookies.is_empty() {
         writeln!(output, "          headers: {{")?;
         for (key, value) in &tool.call.headers {
             writeln!(output, "            \"{key}\": {},", display_value(value))?;
         }
+        if !tool.call.cookies.is_empty() {
+            // Cookies are sent as a single `Cookie` header, leaving out the ones without a value
+            writeln!(output, "            \"Cookie\": [")?;
+            for (key, value) in &tool.call.cookies {
+                writeln!(
+                    output,
+                    "              [\"{key}\", {}],",
+                    display_value(value)
+                )?;
+            }
+            writeln!(
+                output,
+                "            ].filter(([, value]) => value !== undefined).map(([name, value]) => `${{name}}=${{encodeURIComponent(value!)}}`).join(\"; \") || undefined,"
+            )?;
+        }
         writeln!(output, "          }},")?;
     }
 
     if let Some(body) = &tool.call.body {
-        writeln!(output, "          body: {},", display_value(body))?;
+        writeln!(output, "          body: {},", body_to_code(tool, body)?)?;
     }
 
     writeln!(output, "        }})")?;
 
-    writeln!(
-        output,
-        "        .then((response: Response) => response.text());"
-    )?;
-    writeln!(output, "")?;
+    if matches!(tool.call.method, Method::HEAD | Method::OPTIONS) {
+        // the answer is in the status and headers, the body is empty
+        writeln!(
+            output,
+            "        .then((response: Response) => [`${{response.status}} ${{response.statusText}}`, ...[...response.headers].map(([name, value]) => `${{name}}: ${{value}}`)].join(\"\\n\"));"
+        )?;
+    } else {
+        writeln!(
+            output,
+            "        .then((response: Response) => response.text());"
+        )?;
+    }
+    writeln!(output)?;
     writeln!(output, "        return {{")?;
     writeln!(output, "          content: [")?;
     writeln!(output, "            {{")?;
@@ -160,6 +287,222 @@ fn tool_to_code(mcp_server: &MCPServer, tool: &MCPTool) -> anyhow::Result<String
     Ok(output)
 }
 
+const FILE_HELPERS: &str = r#"
+type FileInput = string | { uri: string; mimeType?: string; text?: string; blob?: string };
+
+function base64ToBytes(base64: string) {
+  return Uint8Array.from(atob(base64), (c) => c.charCodeAt(0));
+}
+
+function fileToBlob(file: FileInput, contentType?: string): Blob {
+  if (typeof file === "string") {
+    return new Blob([base64ToBytes(file)], { type: contentType });
+  }
+  const contents = file.blob !== undefined ? base64ToBytes(file.blob) : new TextEncoder().encode(file.text ?? "");
+  return new Blob([contents], { type: file.mimeType ?? contentType });
+}
+
+function fileName(file: FileInput, fallback: string): string {
+  return typeof file === "string" ? fallback : file.uri.split("/").pop() || fallback;
+}
+"#;
+
+const FORM_URL_ENCODED_HELPER: &str = r#"
+function toUrlSearchParams(body: Record<string, unknown> | undefined): URLSearchParams {
+  const params = new URLSearchParams();
+  for (const [key, value] of Object.entries(body ?? {})) {
+    for (const item of Array.isArray(value) ? value : [value]) {
+      if (item === undefined || item === null) continue;
+      params.append(key, typeof item === "object" ? JSON.stringify(item) : String(item));
+    }
+  }
+  return params;
+}
+"#;
+
+const MULTIPART_HELPER: &str = r#"
+function toFormData(body: Record<string, unknown> | undefined, files: string[]): FormData {
+  const formData = new FormData();
+  for (const [key, value] of Object.entries(body ?? {})) {
+    for (const item of Array.isArray(value) ? value : [value]) {
+      if (item === undefined || item === null) continue;
+      if (files.includes(key)) {
+        formData.append(key, fileToBlob(item as FileInput), fileName(item as FileInput, key));
+      } else {
+        formData.append(key, typeof item === "object" ? JSON.stringify(item) : String(item));
+      }
+    }
+  }
+  return formData;
+}
+"#;
+
+const SERVER_CALL_HELPER: &str = r#"
+type ServerCall = {
+  path: string;
+  method: string;
+  pathParams?: Record<string, string | undefined>;
+  query?: Record<string, string | undefined>;
+  headers?: Record<string, string | undefined>;
+  body?: BodyInit;
+  authorizationHeader?: string;
+};
+
+async function callServer(baseUrl: string, request: ServerCall): Promise<Response> {
+  let path = request.path;
+  for (const [name, value] of Object.entries(request.pathParams ?? {})) {
+    path = path.replace(`{${name}}`, encodeURIComponent(value ?? ""));
+  }
+  const url = new URL(baseUrl.replace(/\/+$/, "") + path);
+  for (const [name, value] of Object.entries(request.query ?? {})) {
+    if (value !== undefined) url.searchParams.append(name, value);
+  }
+  const headers = new Headers();
+  for (const [name, value] of Object.entries(request.headers ?? {})) {
+    if (value !== undefined) headers.set(name, value);
+  }
+  if (request.authorizationHeader) headers.set("Authorization", request.authorizationHeader);
+  return fetch(url, { method: request.method, headers, body: request.body });
+}
+"#;
+
+const PATH_PARAMETER_HELPER: &str = r#"
+function serializePathParameter(name: string, value: unknown, style: string, explode: boolean): string {
+  if (value === undefined || value === null) return "";
+  const encode = (item: unknown) => encodeURIComponent(String(item));
+  const prefix = style === "label" ? "." : "";
+  const separator = style === "label" && explode ? "." : ",";
+  if (Array.isArray(value)) {
+    if (style === "matrix") {
+      return explode ? value.map((item) => `;${name}=${encode(item)}`).join("") : `;${name}=${value.map(encode).join(",")}`;
+    }
+    return prefix + value.map(encode).join(separator);
+  }
+  if (typeof value === "object") {
+    const entries = Object.entries(value).filter(([, item]) => item !== undefined);
+    if (style === "matrix") {
+      return explode
+        ? entries.map(([key, item]) => `;${key}=${encode(item)}`).join("")
+        : `;${name}=${entries.map(([key, item]) => `${key},${encode(item)}`).join(",")}`;
+    }
+    return prefix + entries.map(([key, item]) => (explode ? `${key}=${encode(item)}` : `${key},${encode(item)}`)).join(separator);
+  }
+  return style === "matrix" ? `;${name}=${encode(value)}` : prefix + encode(value);
+}
+"#;
+
+const QUERY_HELPER: &str = r#"
+function serializeQuery(parameters: [string, unknown, string, boolean][]): string {
+  const encode = (item: unknown) => encodeURIComponent(typeof item === "object" ? JSON.stringify(item) : String(item));
+  const parts: string[] = [];
+  for (const [name, value, style, explode] of parameters) {
+    if (value === undefined || value === null) continue;
+    const key = encodeURIComponent(name);
+    if (Array.isArray(value)) {
+      if (style === "form" && explode) {
+        parts.push(...value.map((item) => `${key}=${encode(item)}`));
+      } else {
+        const separator = style === "spaceDelimited" ? "%20" : style === "pipeDelimited" ? "|" : ",";
+        parts.push(`${key}=${value.map(encode).join(separator)}`);
+      }
+    } else if (typeof value === "object") {
+      const entries = Object.entries(value).filter(([, item]) => item !== undefined);
+      if (style === "deepObject") {
+        parts.push(...entries.map(([property, item]) => `${key}[${encodeURIComponent(property)}]=${encode(item)}`));
+      } else if (explode) {
+        parts.push(...entries.map(([property, item]) => `${encodeURIComponent(property)}=${encode(item)}`));
+      } else {
+        parts.push(`${key}=${entries.map(([property, item]) => `${encodeURIComponent(property)},${encode(item)}`).join(",")}`);
+      }
+    } else {
+      parts.push(`${key}=${encode(value)}`);
+    }
+  }
+  return parts.length > 0 ? `?${parts.join("&")}` : "";
+}
+"#;
+
+/// Whether `toString()` serializes the parameter correctly, which is the case for primitive values
+/// in the default style.
+fn is_plain(tool: &MCPTool, parameter: &ParameterValue) -> bool {
+    let primitive = match &parameter.value {
+        ValueSource::Fixed(_) => true,
+        ValueSource::Property(id) => tool
+            .properties
+            .iter()
+            .find(|property| property.name == id.to_string())
+            .is_none_or(|property| {
+                matches!(
+                    property.type_,
+                    MCPToolPropertyType::String
+                        | MCPToolPropertyType::Number
+                        | MCPToolPropertyType::Integer
+                        | MCPToolPropertyType::Boolean
+                        | MCPToolPropertyType::Literal(_)
+                        | MCPToolPropertyType::Enum(_)
+                )
+            }),
+    };
+    primitive
+        && matches!(
+            parameter.style,
+            ParameterStyle::Form | ParameterStyle::Simple
+        )
+}
+
+/// The value itself, rather than its string representation.
+fn raw_value(value: &ValueSource) -> String {
+    match value {
+        ValueSource::Fixed(value) => value.to_string(),
+        ValueSource::Property(property) => format!("args.{property}"),
+    }
+}
+
+fn body_to_code(tool: &MCPTool, body: &Body) -> anyhow::Result<String> {
+    let value = raw_value(&body.value);
+    let code = match body.encoding {
+        BodyEncoding::Json => format!("JSON.stringify({value})"),
+        BodyEncoding::FormUrlEncoded => format!("toUrlSearchParams({value})"),
+        BodyEncoding::Multipart => {
+            let body_property = match &body.value {
+                ValueSource::Property(id) => tool
+                    .properties
+                    .iter()
+                    .find(|property| property.name == id.to_string()),
+                ValueSource::Fixed(_) => None,
+            };
+            let files: Vec<&String> = match body_property.map(|property| &property.type_) {
+                Some(MCPToolPropertyType::Object(object)) => object
+                    .iter()
+                    .filter(|(_, property)| match &property.type_ {
+                        MCPToolPropertyType::File => true,
+                        MCPToolPropertyType::Array(items) => {
+                            matches!(items.type_, MCPToolPropertyType::File)
+                        }
+                        _ => false,
+                    })
+                    .map(|(name, _)| name)
+                    .collect(),
+                _ => Vec::new(),
+            };
+            format!("toFormData({value}, {})", serde_json::to_string(&files)?)
+        }
+        BodyEncoding::Text => value,
+        BodyEncoding::Binary => {
+            let content_type = tool
+                .call
+                .headers
+                .get("Content-Type")
+                .map(ToString::to_string);
+            format!(
+                "{value} === undefined ? undefined : fileToBlob({value}, {})",
+                content_type.as_deref().unwrap_or("undefined")
+            )
+        }
+    };
+    Ok(code)
+}
+
 fn generate_zod_schema_from_tool(tool: &MCPTool) -> anyhow::Result<String> {
     let mut zod_fields = String::new();
 
@@ -169,7 +512,7 @@ fn generate_zod_schema_from_tool(tool: &MCPTool) -> anyhow::Result<String> {
 
         let zod_type = mcp_tool_property_to_zod_type(property, 2)?;
 
-        writeln!(zod_fields, "    {}: {}", property.name, zod_type)?;
+        writeln!(zod_fields, "    {}: {},", property.name, zod_type)?;
     }
 
     Ok(format!("{{\n{}  }}", zod_fields))
@@ -184,12 +527,50 @@ fn mcp_tool_property_to_zod_type(
     match &property.type_ {
         MCPToolPropertyType::String => write!(output, "z.string()")?,
         MCPToolPropertyType::Number => write!(output, "z.number()")?,
+        MCPToolPropertyType::Integer => write!(output, "z.number().int()")?,
         MCPToolPropertyType::Boolean => write!(output, "z.boolean()")?,
+        MCPToolPropertyType::Any => write!(output, "z.any()")?,
+        MCPToolPropertyType::Reference(name) => write!(output, "{name}")?,
+        MCPToolPropertyType::File => {
+            writeln!(output, "z.union([")?;
+            writeln!(
+                output,
+                "{ind_str}  z.string().describe(\"Base64 encoded contents\"),"
+            )?;
+            writeln!(
+                output,
+                "{ind_str}  z.object({{ uri: z.string(), mimeType: z.string().optional(), text: z.string().optional(), blob: z.string().optional() }}).describe(\"MCP resource with text or base64 encoded blob contents\"),"
+            )?;
+            write!(output, "{ind_str}])")?;
+        }
+        MCPToolPropertyType::Enum(values) if values.iter().all(|value| value.is_string()) => {
+            write!(output, "z.enum({})", serde_json::to_string(values)?)?
+        }
+        MCPToolPropertyType::Enum(values) => {
+            let literals = values
+                .iter()
+                .map(|value| Ok(format!("z.literal({})", serde_json::to_string(value)?)))
+                .collect::<anyhow::Result<Vec<_>>>()?;
+            write!(output, "z.union([{}])", literals.join(", "))?
+        }
+        MCPToolPropertyType::Literal(value) => {
+            write!(output, "z.literal({})", serde_json::to_string(value)?)?
+        }
         MCPToolPropertyType::Array(property) => {
             writeln!(output, "z.array(")?;
-            write!(
+            writeln!(
                 output,
-                "{ind_str}  {}",
+                "{ind_str}  {},",
+                mcp_tool_property_to_zod_type(property, indentation + 1)?
+            )?;
+            write!(output, "{ind_str})")?;
+        }
+        MCPToolPropertyType::Map(property) => {
+            writeln!(output, "z.record(")?;
+            writeln!(output, "{ind_str}  z.string(),")?;
+            writeln!(
+                output,
+                "{ind_str}  {},",
                 mcp_tool_property_to_zod_type(property, indentation + 1)?
             )?;
             write!(output, "{ind_str})")?;
@@ -197,20 +578,66 @@ fn mcp_tool_property_to_zod_type(
         MCPToolPropertyType::Object(hash_map) => {
             writeln!(output, "z.object({{")?;
             for (name, type_) in hash_map.iter() {
-                write!(
+                writeln!(
                     output,
-                    "{ind_str}  \"{}\": {}",
+                    "{ind_str}  \"{}\": {},",
                     name,
                     mcp_tool_property_to_zod_type(type_, indentation + 1)?
                 )?;
             }
             write!(output, "{ind_str}}})")?;
         }
+        MCPToolPropertyType::Union {
+            variants,
+            discriminator,
+        } => {
+            match discriminator {
+                Some(discriminator) => {
+                    writeln!(output, "z.discriminatedUnion(\"{discriminator}\", [")?
+                }
+                None => writeln!(output, "z.union([")?,
+            }
+            for variant in variants {
+                writeln!(
+                    output,
+                    "{ind_str}  {},",
+                    mcp_tool_property_to_zod_type(variant, indentation + 1)?
+                )?;
+            }
+            write!(output, "{ind_str}])")?;
+        }
+        MCPToolPropertyType::Intersection(parts) => {
+            // `z.intersection` only takes two schemas, chain the rest with `.and()`
+            let mut parts = parts.iter();
+            if let Some(first) = parts.next() {
+                write!(
+                    output,
+                    "{}",
+                    mcp_tool_property_to_zod_type(first, indentation)?
+                )?;
+            }
+            for part in parts {
+                writeln!(output, ".and(")?;
+                writeln!(
+                    output,
+                    "{ind_str}  {},",
+                    mcp_tool_property_to_zod_type(part, indentation + 1)?
+                )?;
+                write!(output, "{ind_str})")?;
+            }
+        }
     };
 
+    write_constraints(&mut output, property)?;
+
+    if property.nullable {
+        write!(output, ".nullable()")?;
+    }
+
     match &property.required {
-        MCPToolPropertyRequired::Default(_) | MCPToolPropertyRequired::Optional => {
-            write!(output, ".optional()")?;
+        MCPToolPropertyRequired::Optional => write!(output, ".optional()")?,
+        MCPToolPropertyRequired::Default(value) => {
+            write!(output, ".default({})", serde_json::to_string(value)?)?
         }
         MCPToolPropertyRequired::Required => {}
     }
@@ -219,11 +646,75 @@ fn mcp_tool_property_to_zod_type(
         write!(output, ".describe(\"{}\")", comment(description))?;
     }
 
-    write!(output, ",")?;
-
     Ok(output)
 }
 
+fn write_constraints(output: &mut String, property: &MCPToolProperty) -> anyhow::Result<()> {
+    let constraints = &property.constraints;
+    match &property.type_ {
+        MCPToolPropertyType::String => {
+            if let Some(min_length) = constraints.min_length {
+                write!(output, ".min({min_length})")?;
+            }
+            if let Some(max_length) = constraints.max_length {
+                write!(output, ".max({max_length})")?;
+            }
+            if let Some(pattern) = &constraints.pattern {
+                write!(
+                    output,
+                    ".regex(new RegExp({}))",
+                    serde_json::to_string(pattern)?
+                )?;
+            }
+            match constraints.format.as_deref() {
+                Some("email") => write!(output, ".email()")?,
+                Some("uuid") => write!(output, ".uuid()")?,
+                Some("date-time") => write!(output, ".datetime({{ offset: true }})")?,
+                Some("uri") | Some("url") => write!(output, ".url()")?,
+                Some("ipv4") => write!(output, ".ip({{ version: \"v4\" }})")?,
+                Some("ipv6") => write!(output, ".ip({{ version: \"v6\" }})")?,
+                _ => {}
+            }
+        }
+        MCPToolPropertyType::Number | MCPToolPropertyType::Integer => {
+            if let Some(minimum) = constraints.minimum {
+                match constraints.exclusive_minimum {
+                    true => write!(output, ".gt({minimum})")?,
+                    false => write!(output, ".gte({minimum})")?,
+                }
+            }
+            if let Some(maximum) = constraints.maximum {
+                match constraints.exclusive_maximum {
+                    true => write!(output, ".lt({maximum})")?,
+                    false => write!(output, ".lte({maximum})")?,
+                }
+            }
+            if let Some(multiple_of) = constraints.multiple_of {
+                write!(output, ".multipleOf({multiple_of})")?;
+            }
+        }
+        MCPToolPropertyType::Object(_) if constraints.additional_properties => {
+            write!(output, ".passthrough()")?;
+        }
+        MCPToolPropertyType::Array(_) => {
+            if let Some(min_items) = constraints.min_items {
+                write!(output, ".min({min_items})")?;
+            }
+            if let Some(max_items) = constraints.max_items {
+                write!(output, ".max({max_items})")?;
+            }
+            if constraints.unique_items {
+                write!(
+                    output,
+                    ".refine((items) => new Set(items.map((item) => JSON.stringify(item))).size === items.length, \"Items must be unique\")"
+                )?;
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
 fn comment(s: &str) -> String {
     s.replace("\r\n", "\n")
         .replace("\n", "\\n")
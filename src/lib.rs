@@ -2,6 +2,7 @@ pub mod codegen_typescript;
 pub mod mcp_server;
 pub mod template;
 
+mod spec;
 mod template_features;
 mod template_files;
 
@@ -15,6 +16,13 @@ pub use crate::mcp_server::MCPServer;
 
 pub type GenerateOptions = ConverterOptions;
 
+/// Report of [`generate_with_report`] on the tools it generated.
+#[derive(Debug, Clone, Default)]
+pub struct GenerateReport {
+    /// Tools whose names were shortened to fit the maximum length, as `(original, shortened)`.
+    pub shortened_tool_names: Vec<(String, String)>,
+}
+
 /// Generate MCP server code from an OpenAPI spec
 ///
 /// ## Arguments
@@ -25,6 +33,16 @@ pub fn generate(
     project_path: impl AsRef<Path>,
     options: GenerateOptions,
 ) -> anyhow::Result<()> {
+    generate_with_report(openapi_path, project_path, options).map(|_| ())
+}
+
+/// Generate MCP server code from an OpenAPI spec like [`generate`], returning a report of the
+/// tools whose names were shortened.
+pub fn generate_with_report(
+    openapi_path: impl AsRef<Path>,
+    project_path: impl AsRef<Path>,
+    options: GenerateOptions,
+) -> anyhow::Result<GenerateReport> {
     let openapi_path = openapi_path.as_ref();
     let project_path = project_path.as_ref();
 
@@ -34,7 +52,7 @@ pub fn generate(
         project_path = project_path.to_string_lossy(),
     );
 
-    let openapi = parse_openapi_spec_from_path(openapi_path)?;
+    let openapi = parse_openapi_spec_with_overlays(openapi_path, &options.overlays)?;
     let mcp_server = MCPServer::from_openapi(openapi, options)?;
 
     let tools_code_path = project_path.join("src/routes/v1/mcp/tools/");
@@ -54,8 +72,8 @@ pub fn generate(
         fs::remove_file(echo_path)?;
     }
 
-    template::update_tools_index_ts(&mcp_server, &project_path)?;
-    template::update_constants_ts(&mcp_server, &project_path)?;
+    template::update_tools_index_ts(&mcp_server, project_path)?;
+    template::update_constants_ts(&mcp_server, project_path)?;
 
     let features = template_features::Features {
         auth: mcp_server.oauth2_info.is_some(),
@@ -70,20 +88,51 @@ pub fn generate(
         }
     }
 
-    Ok(())
+    Ok(GenerateReport {
+        shortened_tool_names: mcp_server
+            .tools
+            .into_iter()
+            .filter_map(|tool| Some((tool.original_name?, tool.name)))
+            .collect(),
+    })
 }
 
+/// Parse an OpenAPI spec from a JSON or YAML file.
+///
+/// References to other files (e.g. `./common.yaml#/components/schemas/Error`) are resolved
+/// relative to the spec and bundled into the returned document. Swagger 2.0 and OpenAPI 3.1 specs
+/// are rewritten into OpenAPI 3.0, what is lost on the way is logged as a warning.
 pub fn parse_openapi_spec_from_path<P: AsRef<Path>>(path: P) -> anyhow::Result<OpenAPI> {
-    let content = fs::read_to_string(&path)?;
-
-    let extension = path.as_ref().extension().and_then(|s| s.to_str());
-    let openapi: OpenAPI = match extension {
-        Some("json") => serde_json::from_str(&content)
-            .map_err(|e| anyhow::anyhow!("Failed to parse JSON: {}", e))?,
-        Some("yaml") | Some("yml") => serde_yaml::from_str(&content)
-            .map_err(|e| anyhow::anyhow!("Failed to parse YAML: {}", e))?,
-        _ => Err(anyhow::anyhow!("Unsupported file extension"))?,
-    };
+    parse_openapi_spec_with_overlays(path, &[] as &[&Path])
+}
+
+/// Parse an OpenAPI spec like [`parse_openapi_spec_from_path`], applying the OpenAPI Overlay
+/// documents at `overlay_paths` to it in order.
+///
+/// Overlays are applied to the spec as it is written, before it is bundled and converted, so
+/// their targets match the spec the vendor ships. Only the file at `path` is overlaid, not the
+/// files it references.
+pub fn parse_openapi_spec_with_overlays<P: AsRef<Path>>(
+    path: impl AsRef<Path>,
+    overlay_paths: &[P],
+) -> anyhow::Result<OpenAPI> {
+    let path = path.as_ref();
+    let mut document = spec::read_document(path)?;
+    for overlay_path in overlay_paths {
+        let overlay_path = overlay_path.as_ref();
+        let overlay = spec::read_document(overlay_path)?;
+        spec::apply_overlay(&mut document, &overlay).map_err(|e| {
+            anyhow::anyhow!("Failed to apply overlay {}: {}", overlay_path.display(), e)
+        })?;
+    }
+    spec::bundle_external_refs(&mut document, path.parent().unwrap_or(Path::new("")))?;
+    spec::upgrade_swagger_2(&mut document)?;
+    spec::downgrade_openapi_31(&mut document);
+    spec::inline_path_item_refs(&mut document)?;
+    spec::const_to_enum(&mut document);
+
+    let openapi: OpenAPI = serde_json::from_value(document)
+        .map_err(|e| anyhow::anyhow!("Failed to parse OpenAPI spec: {}", e))?;
 
     validate_spec(&openapi)?;
     Ok(openapi)
@@ -92,7 +141,7 @@ pub fn parse_openapi_spec_from_path<P: AsRef<Path>>(path: P) -> anyhow::Result<O
 fn validate_spec(spec: &OpenAPI) -> anyhow::Result<()> {
     if !spec.openapi.starts_with("3.") {
         Err(anyhow::anyhow!(
-            "Only OpenAPI 3.x specifications are supported"
+            "Only Swagger 2.0 and OpenAPI 3.x specifications are supported"
         ))?;
     }
 
@@ -104,15 +153,5 @@ fn validate_spec(spec: &OpenAPI) -> anyhow::Result<()> {
         Err(anyhow::anyhow!("OpenAPI spec must have at least one path"))?;
     }
 
-    // Validate that all references can be resolved
-    for (_, path_item_ref) in &spec.paths.paths {
-        if let openapiv3::ReferenceOr::Reference { reference } = path_item_ref {
-            Err(anyhow::anyhow!(
-                "Path item references are not supported: {}",
-                reference
-            ))?;
-        }
-    }
-
     Ok(())
 }
@@ -20,6 +20,30 @@ struct Cli {
     #[arg(long, value_delimiter = ',')]
     include_methods: Vec<String>,
 
+    /// Expression the operations must match to be included, e.g.
+    /// `tag:mail AND method:GET AND NOT deprecated`. If not provided, all operations will be included.
+    #[arg(long)]
+    include_operations: Option<String>,
+
+    /// Expression excluding the operations that match it, e.g. `operationId:*Beta* OR deprecated`.
+    #[arg(long)]
+    exclude_operations: Option<String>,
+
+    /// How tool names are derived from operations: `path`, `operation-id`, `tag-operation-id` or a
+    /// template like `{tag}_{operationId}`. Default is `path`.
+    #[arg(long)]
+    tool_naming: Option<String>,
+
+    /// Case the tool names are converted to: `snake`, `camel`, `pascal`, `kebab` or `preserve`.
+    /// Default is `snake`.
+    #[arg(long)]
+    tool_name_case: Option<String>,
+
+    /// Restrictions of the clients the tool names have to satisfy: `mcp` for the MCP character set
+    /// `[A-Za-z0-9_-]` or `strict` to also limit them to 64 characters. Default is `mcp`.
+    #[arg(long)]
+    client_profile: Option<String>,
+
     /// Maximum length of the tool name. Default is `DEFAULT_MAX_TOOL_NAME_LENGTH`.
     #[arg(long)]
     max_tool_name_length: Option<u32>,
@@ -30,6 +54,26 @@ struct Cli {
     #[arg(long, default_value_t = false)]
     skip_long_tool_names: bool,
 
+    /// Shorten tool names that exceed the maximum length instead of skipping them or failing.
+    /// Shortened tools are printed along with their original names.
+    #[arg(long, default_value_t = false)]
+    shorten_long_tool_names: bool,
+
+    /// Maximum nesting depth of a schema, deeper schemas accept any value. Default is
+    /// `DEFAULT_MAX_SCHEMA_DEPTH`.
+    #[arg(long)]
+    max_schema_depth: Option<u32>,
+
+    /// Server the tools call: its index in the servers of the spec, its URL or description, or any
+    /// other URL. Default is the first server of the spec.
+    #[arg(long)]
+    server: Option<String>,
+
+    /// Path of an OpenAPI Overlay document to apply to the spec before generating the tools. Can
+    /// be given several times, the overlays are applied in order.
+    #[arg(long = "overlay")]
+    overlays: Vec<PathBuf>,
+
     /// Enable OAuth2 authentication.
     #[arg(long, default_value_t = false)]
     oauth2: bool,
@@ -47,18 +91,60 @@ struct Cli {
     oauth2_refresh_url: Option<String>,
 }
 
+/// Prints warnings, e.g. about parts of the spec that can't be converted, to stderr.
+struct StderrLogger;
+
+impl log::Log for StderrLogger {
+    fn enabled(&self, metadata: &log::Metadata) -> bool {
+        metadata.level() <= log::Level::Warn
+    }
+
+    fn log(&self, record: &log::Record) {
+        if self.enabled(record.metadata()) {
+            eprintln!("{}: {}", record.level(), record.args());
+        }
+    }
+
+    fn flush(&self) {}
+}
+
 fn main() -> anyhow::Result<()> {
     let cli = Cli::parse();
+    log::set_logger(&StderrLogger).map_err(anyhow::Error::msg)?;
+    log::set_max_level(log::LevelFilter::Warn);
     let include_methods = cli
         .include_methods
         .into_iter()
-        .map(|method| http::Method::from_bytes(&method.as_bytes()).unwrap())
-        .collect();
+        .map(|method| http::Method::from_bytes(method.to_uppercase().as_bytes()))
+        .collect::<Result<_, _>>()?;
     let include_tools = match cli.include_tools {
         Some(r) => Some(Regex::new(&r)?),
         None => None,
     };
 
+    let include_operations = match cli.include_operations {
+        Some(filter) => Some(filter.parse().map_err(anyhow::Error::msg)?),
+        None => None,
+    };
+    let exclude_operations = match cli.exclude_operations {
+        Some(filter) => Some(filter.parse().map_err(anyhow::Error::msg)?),
+        None => None,
+    };
+
+    let tool_naming = match cli.tool_naming {
+        Some(naming) => naming.parse().map_err(anyhow::Error::msg)?,
+        None => Default::default(),
+    };
+    let tool_name_case = match cli.tool_name_case {
+        Some(case) => case.parse().map_err(anyhow::Error::msg)?,
+        None => Default::default(),
+    };
+
+    let client_profile = match cli.client_profile {
+        Some(profile) => profile.parse().map_err(anyhow::Error::msg)?,
+        None => Default::default(),
+    };
+
     let oauth2_info = cli.oauth2.then(|| openapiv3::AuthorizationCodeOAuth2Flow {
         authorization_url: cli.oauth2_auth_url.unwrap(),
         token_url: cli.oauth2_token_url.unwrap(),
@@ -67,18 +153,29 @@ fn main() -> anyhow::Result<()> {
         extensions: Default::default(),
     });
 
-    openapi2mcp::generate(
+    let report = openapi2mcp::generate_with_report(
         &cli.input,
         &cli.project_path,
         openapi2mcp::GenerateOptions {
             include_tools,
             include_methods,
+            include_operations,
+            exclude_operations,
+            tool_naming,
+            tool_name_case,
+            client_profile,
             max_tool_name_length: cli.max_tool_name_length,
             skip_long_tool_names: cli.skip_long_tool_names,
+            shorten_long_tool_names: cli.shorten_long_tool_names,
+            max_schema_depth: cli.max_schema_depth,
+            server: cli.server,
+            overlays: cli.overlays,
             oauth2_info,
-            ..Default::default()
         },
-    )
-    .expect("failed to generate MCP");
+    )?;
+
+    for (original_name, name) in &report.shortened_tool_names {
+        eprintln!("Shortened tool {original_name} to {name}");
+    }
     Ok(())
 }
@@ -1,15 +1,30 @@
-use std::collections::{BTreeMap, HashMap, HashSet};
+use std::{
+    borrow::Cow,
+    collections::{BTreeMap, HashMap},
+    path::PathBuf,
+};
 
 use convert_case::Casing;
 use http::Method;
 use openapiv3::{
-    OAuth2Flows, OpenAPI, Parameter, PathItem, ReferenceOr, RequestBody, Schema, SecurityScheme,
+    OAuth2Flows, OpenAPI, Parameter, PathItem, PathStyle, QueryStyle, ReferenceOr, SecurityScheme,
+    Server,
 };
 use regex::Regex;
 
+use crate::mcp_server::filter::OperationFilter;
+use crate::mcp_server::naming::{
+    ClientProfile, ToolNameCase, ToolNaming, deduplicate_tool_names, disambiguate_shortened_names,
+    shorten_tool_name, tool_name,
+};
+use crate::mcp_server::reference::{
+    resolve_parameter, resolve_path, resolve_request_body, resolve_security_scheme,
+};
+use crate::mcp_server::schema::{DEFAULT_MAX_SCHEMA_DEPTH, SchemaConverter};
 use crate::mcp_server::{
-    Call, MCPServer, MCPTool, MCPToolProperty, MCPToolPropertyRequired, MCPToolPropertyType,
-    PropertyId, Value, ValueSource,
+    ApiServer, Body, BodyEncoding, Call, MCPServer, MCPTool, MCPToolProperty,
+    MCPToolPropertyRequired, MCPToolPropertyType, ParameterStyle, ParameterValue, PropertyId,
+    ServerVariable, Value, ValueSource,
 };
 
 pub const DEFAULT_MAX_TOOL_NAME_LENGTH: u32 = 80;
@@ -20,12 +35,36 @@ pub struct ConverterOptions {
     pub include_tools: Option<Regex>,
     /// Methods to include in the MCP server. If not provided, all methods will be included.
     pub include_methods: Vec<http::Method>,
-    /// Maximum length of the tool name. Default is `DEFAULT_MAX_TOOL_NAME_LENGTH`.
+    /// Expression the operations must match to be included. If not provided, all operations will
+    /// be included.
+    pub include_operations: Option<OperationFilter>,
+    /// Expression excluding the operations that match it.
+    pub exclude_operations: Option<OperationFilter>,
+    /// How tool names are derived from operations. Default is `ToolNaming::Path`.
+    pub tool_naming: ToolNaming,
+    /// Case the tool names are converted to. Default is `ToolNameCase::Snake`.
+    pub tool_name_case: ToolNameCase,
+    /// Restrictions of the clients the tool names have to satisfy. Default is `ClientProfile::Mcp`.
+    pub client_profile: ClientProfile,
+    /// Maximum length of the tool name. Default is `DEFAULT_MAX_TOOL_NAME_LENGTH`, lowered to the
+    /// limit of the client profile if it has one.
     pub max_tool_name_length: Option<u32>,
     /// Skip tool names that exceed the maximum length. Default is `false`.
     /// If true, the tool will be skipped and the next tool will be processed.
     /// If false, the tool throw an error.
     pub skip_long_tool_names: bool,
+    /// Shorten tool names that exceed the maximum length instead of skipping them or failing.
+    /// Default is `false`. Takes precedence over `skip_long_tool_names`.
+    pub shorten_long_tool_names: bool,
+    /// Maximum nesting depth of a schema, deeper schemas accept any value. Default is
+    /// `DEFAULT_MAX_SCHEMA_DEPTH`.
+    pub max_schema_depth: Option<u32>,
+    /// Server the tools call, given as its index in the servers of the spec, its URL or
+    /// description, or any other URL. Default is the first server of the spec.
+    pub server: Option<String>,
+    /// Paths of OpenAPI Overlay documents that [`crate::generate`] applies to the spec, in order,
+    /// before converting it.
+    pub overlays: Vec<PathBuf>,
     /// OAuth2 information.
     pub oauth2_info: Option<openapiv3::AuthorizationCodeOAuth2Flow>,
 }
@@ -34,128 +73,286 @@ pub fn openapi_to_mcp_server(
     openapi: OpenAPI,
     options: ConverterOptions,
 ) -> anyhow::Result<MCPServer> {
-    let oauth2_info_from_spec =
-        get_oauth2_info(&openapi).and_then(|info| info.authorization_code.as_ref());
+    let oauth2_info_from_spec = get_oauth2_info(&openapi).and_then(|info| info.authorization_code);
     let oauth2_info_from_options = options.oauth2_info.clone();
-    let oauth2_info = oauth2_info_from_options.or_else(|| oauth2_info_from_spec.cloned());
+    let oauth2_info = oauth2_info_from_options.or(oauth2_info_from_spec);
 
     let include_methods = &options.include_methods;
     let include_tools = &options.include_tools;
 
+    let servers: Vec<_> = openapi.servers.iter().map(api_server).collect();
+    let server = choose_server(&servers, options.server.as_deref())?;
+
+    // the variables of the servers of operations and paths can be overridden like the ones of the
+    // API server, see `add_server_variables`
+    let mut server_variables = server.variables.clone();
     let mut tools = Vec::new();
+    let mut filtered_out = 0;
+    let mut too_long = 0;
     for (path, path_item_ref) in &openapi.paths.paths {
-        if matches!(&include_tools, Some(regex) if !regex.is_match(path)) {
-            continue;
-        }
-
-        let path_item = resolve_path(&openapi, path_item_ref).unwrap();
+        let path_item = resolve_path(&openapi, path_item_ref)?;
 
-        if let Some(operation) = &path_item.get {
-            if !include_methods.is_empty() && !include_methods.contains(&Method::GET) {
+        for (method, operation) in operations(&path_item) {
+            if method == Method::TRACE {
+                // `fetch` throws on TRACE requests, so the tool could never succeed
+                log::warn!(
+                    "Skipped TRACE tool for path: {}, the HTTP client can't send TRACE requests",
+                    path
+                );
+                filtered_out += 1;
                 continue;
             }
-            let tool = operation_to_tool(
-                Method::GET,
-                path,
-                operation,
-                &path_item.parameters,
-                &openapi,
-                &options,
-            )?;
-            if let Some(tool) = tool {
-                tools.push(tool);
-            }
-            log::info!("Added GET tool for path: {}", path);
-        }
-        if let Some(operation) = &path_item.post {
-            if !include_methods.is_empty() && !include_methods.contains(&Method::POST) {
+            if matches!(&include_tools, Some(regex) if !regex.is_match(path)) {
+                log::debug!(
+                    "Skipped {} tool for path: {}, path not included",
+                    method,
+                    path
+                );
+                filtered_out += 1;
                 continue;
             }
-            let tool = operation_to_tool(
-                Method::POST,
-                path,
-                operation,
-                &path_item.parameters,
-                &openapi,
-                &options,
-            )?;
-            if let Some(tool) = tool {
-                tools.push(tool);
-            }
-            log::info!("Added POST tool for path: {}", path);
-        }
-        if let Some(operation) = &path_item.put {
-            if !include_methods.is_empty() && !include_methods.contains(&Method::PUT) {
+            if !include_methods.is_empty() && !include_methods.contains(&method) {
+                log::debug!(
+                    "Skipped {} tool for path: {}, method not included",
+                    method,
+                    path
+                );
+                filtered_out += 1;
                 continue;
             }
-            let tool = operation_to_tool(
-                Method::PUT,
-                path,
-                operation,
-                &path_item.parameters,
-                &openapi,
-                &options,
-            )?;
-            if let Some(tool) = tool {
-                tools.push(tool);
-            }
-            log::info!("Added PUT tool for path: {}", path);
-        }
-        if let Some(operation) = &path_item.delete {
-            if !include_methods.is_empty() && !include_methods.contains(&Method::DELETE) {
-                continue;
-            }
-            let tool = operation_to_tool(
-                Method::DELETE,
-                path,
-                operation,
-                &path_item.parameters,
-                &openapi,
-                &options,
-            )?;
-            if let Some(tool) = tool {
-                tools.push(tool);
-            }
-            log::info!("Added DELETE tool for path: {}", path);
-        }
-        if let Some(operation) = &path_item.patch {
-            if !include_methods.is_empty() && !include_methods.contains(&Method::PATCH) {
+            let included = options
+                .include_operations
+                .as_ref()
+                .is_none_or(|filter| filter.matches(&method, path, operation));
+            let excluded = options
+                .exclude_operations
+                .as_ref()
+                .is_some_and(|filter| filter.matches(&method, path, operation));
+            if !included || excluded {
+                log::debug!("Skipped {} tool for path: {}, filtered out", method, path);
+                filtered_out += 1;
                 continue;
             }
+
             let tool = operation_to_tool(
-                Method::PATCH,
+                method.clone(),
                 path,
                 operation,
                 &path_item.parameters,
                 &openapi,
                 &options,
             )?;
-            if let Some(tool) = tool {
-                tools.push(tool);
+            match tool {
+                Some(mut tool) => {
+                    // operations and paths can be served by other servers than the API
+                    let servers = match operation.servers.is_empty() {
+                        true => &path_item.servers,
+                        false => &operation.servers,
+                    };
+                    if servers.len() > 1 {
+                        log::warn!(
+                            "{} {} has {} servers, its tool calls the first one: {}",
+                            method,
+                            path,
+                            servers.len(),
+                            servers[0].url
+                        );
+                    }
+                    tool.call.base_url = servers
+                        .first()
+                        .filter(|own_server| own_server.url != server.url)
+                        .map(|own_server| {
+                            add_server_variables(&mut server_variables, &api_server(own_server))
+                        });
+
+                    log::info!("Added {} tool for path: {}", method, path);
+                    tools.push(tool);
+                }
+                None => {
+                    log::warn!("Skipped {} tool for path: {}, name too long", method, path);
+                    too_long += 1;
+                }
             }
-            log::info!("Added PATCH tool for path: {}", path);
         }
     }
 
-    log::info!("Created {} MCP tools", tools.len());
+    let max_tool_name_length = max_tool_name_length(&options);
+    if options.shorten_long_tool_names {
+        disambiguate_shortened_names(&mut tools, options.tool_name_case, max_tool_name_length);
+    }
+    deduplicate_tool_names(&mut tools, options.tool_name_case, max_tool_name_length);
+
+    for tool in &tools {
+        anyhow::ensure!(
+            options.client_profile.accepts(&tool.name),
+            "Tool name {:?} of {} {} is empty, too long or uses characters other than [A-Za-z0-9_-]",
+            tool.name,
+            tool.call.method,
+            tool.call.path
+        );
+    }
+
+    log::info!(
+        "Created {} MCP tools, skipped {} filtered out and {} with too long names",
+        tools.len(),
+        filtered_out,
+        too_long
+    );
 
-    // TODO: handle multiple servers
-    anyhow::ensure!(openapi.servers.len() <= 1);
-    let base_url = openapi
-        .servers
-        .first()
-        .map(|s| s.url.clone())
-        .unwrap_or_default();
     Ok(MCPServer {
         name: openapi.info.title,
         version: openapi.info.version,
         description: openapi.info.description,
         tools,
-        base_url,
+        base_url: server.url,
+        base_url_variables: server_variables,
+        servers,
         oauth2_info,
     })
 }
 
+/// Picks the server the tools call, see [`ConverterOptions::server`].
+fn choose_server(servers: &[ApiServer], choice: Option<&str>) -> anyhow::Result<ApiServer> {
+    let Some(choice) = choice else {
+        return Ok(servers.first().cloned().unwrap_or(ApiServer {
+            url: String::new(),
+            description: None,
+            variables: BTreeMap::new(),
+        }));
+    };
+
+    let server = match choice.parse::<usize>() {
+        Ok(index) => servers.get(index),
+        Err(_) => servers.iter().find(|server| {
+            server.url == choice
+                || server
+                    .description
+                    .as_deref()
+                    .is_some_and(|description| description.eq_ignore_ascii_case(choice))
+        }),
+    };
+    match server {
+        Some(server) => Ok(server.clone()),
+        None if choice.contains("://") => Ok(ApiServer {
+            url: choice.to_string(),
+            description: None,
+            variables: BTreeMap::new(),
+        }),
+        None => anyhow::bail!(
+            "Server {} is neither a URL nor one of the servers of the spec: {}",
+            choice,
+            servers
+                .iter()
+                .map(|server| server.url.as_str())
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+    }
+}
+
+fn api_server(server: &Server) -> ApiServer {
+    ApiServer {
+        url: server.url.clone(),
+        description: server.description.clone(),
+        variables: server
+            .variables
+            .iter()
+            .flatten()
+            .map(|(name, variable)| {
+                let variable = ServerVariable {
+                    default: variable.default.clone(),
+                    values: variable.enumeration.clone(),
+                    description: variable.description.clone(),
+                };
+                (name.clone(), variable)
+            })
+            .collect(),
+    }
+}
+
+/// Adds the variables of `server` to `variables` and returns its URL with their names.
+///
+/// A variable is shared with another server that has one with the same name, default and values.
+/// If they differ, it gets a numeric suffix instead, so each server keeps its own values.
+fn add_server_variables(
+    variables: &mut BTreeMap<String, ServerVariable>,
+    server: &ApiServer,
+) -> String {
+    let mut names = BTreeMap::new();
+    for (name, variable) in &server.variables {
+        let mut unique_name = name.clone();
+        let mut suffix = 2;
+        loop {
+            match variables.get(&unique_name) {
+                Some(existing)
+                    if existing.default == variable.default
+                        && existing.values == variable.values =>
+                {
+                    break;
+                }
+                Some(_) => {
+                    unique_name = format!("{name}_{suffix}");
+                    suffix += 1;
+                }
+                None => {
+                    if unique_name != *name {
+                        log::warn!(
+                            "Server variable {} of {} differs from the one of another server, naming it {}",
+                            name,
+                            server.url,
+                            unique_name
+                        );
+                    }
+                    variables.insert(unique_name.clone(), variable.clone());
+                    break;
+                }
+            }
+        }
+        names.insert(name.as_str(), unique_name);
+    }
+
+    // replaced in a single pass, a new name can be the name of another variable
+    let mut url = String::new();
+    let mut rest = server.url.as_str();
+    while let Some(start) = rest.find('{')
+        && let Some(length) = rest[start..].find('}')
+    {
+        let name = &rest[start + 1..start + length];
+        url.push_str(&rest[..=start]);
+        url.push_str(names.get(name).map_or(name, String::as_str));
+        url.push('}');
+        rest = &rest[start + length + 1..];
+    }
+    url.push_str(rest);
+    url
+}
+
+/// The operations of a path item, in the order their tools are generated.
+fn operations(path_item: &PathItem) -> impl Iterator<Item = (Method, &openapiv3::Operation)> {
+    [
+        (Method::GET, &path_item.get),
+        (Method::POST, &path_item.post),
+        (Method::PUT, &path_item.put),
+        (Method::DELETE, &path_item.delete),
+        (Method::PATCH, &path_item.patch),
+        (Method::HEAD, &path_item.head),
+        (Method::OPTIONS, &path_item.options),
+        (Method::TRACE, &path_item.trace),
+    ]
+    .into_iter()
+    .filter_map(|(method, operation)| Some((method, operation.as_ref()?)))
+}
+
+fn max_tool_name_length(options: &ConverterOptions) -> usize {
+    let max_length = options
+        .max_tool_name_length
+        .unwrap_or(DEFAULT_MAX_TOOL_NAME_LENGTH) as usize;
+    match options.client_profile.max_tool_name_length() {
+        Some(profile_max_length) => max_length.min(profile_max_length),
+        None => max_length,
+    }
+}
+
 fn operation_to_tool(
     method: Method,
     path: &str,
@@ -164,21 +361,34 @@ fn operation_to_tool(
     openapi: &OpenAPI,
     options: &ConverterOptions,
 ) -> anyhow::Result<Option<MCPTool>> {
-    let max_tool_name_length = options
-        .max_tool_name_length
-        .unwrap_or(DEFAULT_MAX_TOOL_NAME_LENGTH) as usize;
+    let max_tool_name_length = max_tool_name_length(options);
     let skip_long_tool_names = options.skip_long_tool_names;
 
-    let tool_name = format!(
-        "{}_{}",
-        method.to_string().to_lowercase(),
-        cleanup_string(path)
+    let mut tool_name = tool_name(
+        &options.tool_naming,
+        options.tool_name_case,
+        &method,
+        path,
+        operation,
     );
+    let mut original_name = None;
 
     if tool_name.len() > max_tool_name_length {
-        match skip_long_tool_names {
-            true => return Ok(None),
-            false => anyhow::bail!("Tool name {} exceeded the maximum length", tool_name),
+        if options.shorten_long_tool_names {
+            let short_name = shorten_tool_name(
+                &options.tool_naming,
+                options.tool_name_case,
+                &method,
+                path,
+                operation,
+                max_tool_name_length,
+            );
+            original_name = Some(std::mem::replace(&mut tool_name, short_name));
+        } else {
+            match skip_long_tool_names {
+                true => return Ok(None),
+                false => anyhow::bail!("Tool name {} exceeded the maximum length", tool_name),
+            }
         }
     }
 
@@ -187,29 +397,30 @@ fn operation_to_tool(
         .clone()
         .unwrap_or_else(|| format!("{} {}", method, path));
 
+    let mut schemas = SchemaConverter::new(
+        openapi,
+        options.max_schema_depth.unwrap_or(DEFAULT_MAX_SCHEMA_DEPTH),
+    );
+
     let mut path_params = BTreeMap::new();
     let mut query = BTreeMap::new();
     let mut headers = BTreeMap::new();
+    let mut cookies = BTreeMap::new();
     let mut properties = Vec::new();
     let mut used_property_names = HashMap::new();
     let all_params = operation.parameters.iter().chain(route_params.iter());
 
     // TODO: take another look at the parameters
     for param_ref in all_params {
-        let parameter = resolve_parameter(openapi, param_ref).unwrap();
+        let parameter = resolve_parameter(openapi, param_ref)?;
 
-        let parameter_data = match parameter {
+        let parameter_data = match parameter.as_ref() {
             openapiv3::Parameter::Query { parameter_data, .. } => parameter_data,
             openapiv3::Parameter::Header { parameter_data, .. } => parameter_data,
             openapiv3::Parameter::Path { parameter_data, .. } => parameter_data,
-            openapiv3::Parameter::Cookie { .. } => todo!(),
+            openapiv3::Parameter::Cookie { parameter_data, .. } => parameter_data,
         };
 
-        let required = if parameter_data.required {
-            MCPToolPropertyRequired::Required
-        } else {
-            MCPToolPropertyRequired::Optional
-        };
         // original name is the name of the parameter as it is in the OpenAPI spec
         let original_name = parameter_data.name.clone();
 
@@ -227,141 +438,126 @@ fn operation_to_tool(
             }
         };
 
-        properties.push(MCPToolProperty {
-            name: property_name.clone(),
-            description: parameter_data.description.clone(),
-            required,
-            // TODO: don't hardcode string
-            type_: MCPToolPropertyType::String,
-        });
+        let mut property = schemas
+            .parameter_schema_to_mcp_tool_property(&parameter_data.format)
+            .unwrap_or_else(|| MCPToolProperty {
+                name: String::new(),
+                description: None,
+                required: MCPToolPropertyRequired::Optional,
+                type_: MCPToolPropertyType::String,
+                constraints: Default::default(),
+                nullable: false,
+            });
+        property.name = property_name.clone();
+        property.description = parameter_data.description.clone().or(property.description);
+        if parameter_data.required {
+            property.required = MCPToolPropertyRequired::Required;
+        } else if !matches!(property.required, MCPToolPropertyRequired::Default(_)) {
+            property.required = MCPToolPropertyRequired::Optional;
+        }
 
-        match parameter {
-            openapiv3::Parameter::Query { .. } => {
-                query.insert(
-                    original_name,
-                    ValueSource::Property(PropertyId::from_query(&property_name)),
-                );
+        // parameters that only allow a single value are sent without asking for them
+        let fixed = match &property.type_ {
+            MCPToolPropertyType::Literal(value) => json_to_value(value),
+            _ => None,
+        };
+
+        let property_id = match parameter.as_ref() {
+            openapiv3::Parameter::Query { .. } => PropertyId::from_query(&property_name),
+            openapiv3::Parameter::Header { .. } => PropertyId::from_header(&property_name),
+            openapiv3::Parameter::Path { .. } => PropertyId::from_path(&property_name),
+            openapiv3::Parameter::Cookie { .. } => PropertyId::from_cookie(&property_name),
+        };
+        let value = match fixed {
+            Some(value) => ValueSource::Fixed(value),
+            None => {
+                properties.push(property);
+                ValueSource::Property(property_id)
             }
-            openapiv3::Parameter::Header { .. } => {
-                headers.insert(
+        };
+
+        match parameter.as_ref() {
+            openapiv3::Parameter::Query { style, .. } => {
+                let style = match style {
+                    QueryStyle::Form => ParameterStyle::Form,
+                    QueryStyle::SpaceDelimited => ParameterStyle::SpaceDelimited,
+                    QueryStyle::PipeDelimited => ParameterStyle::PipeDelimited,
+                    QueryStyle::DeepObject => ParameterStyle::DeepObject,
+                };
+                query.insert(
                     original_name,
-                    ValueSource::Property(PropertyId::from_header(&property_name)),
+                    ParameterValue::new(value, style, parameter_data.explode),
                 );
             }
-            openapiv3::Parameter::Path { .. } => {
+            openapiv3::Parameter::Path { style, .. } => {
+                let style = match style {
+                    PathStyle::Simple => ParameterStyle::Simple,
+                    PathStyle::Label => ParameterStyle::Label,
+                    PathStyle::Matrix => ParameterStyle::Matrix,
+                };
                 path_params.insert(
                     original_name,
-                    ValueSource::Property(PropertyId::from_path(&property_name)),
+                    ParameterValue::new(value, style, parameter_data.explode),
                 );
             }
-            openapiv3::Parameter::Cookie { .. } => todo!(),
-        };
+            openapiv3::Parameter::Header { .. } => {
+                headers.insert(original_name, value);
+            }
+            openapiv3::Parameter::Cookie { .. } => {
+                cookies.insert(original_name, value);
+            }
+        }
     }
 
-    fn schema_kind_to_mcp_tool_property<'a>(
-        schema_kind: &'a openapiv3::Schema,
-        openapi: &'a OpenAPI,
-    ) -> Option<MCPToolProperty> {
-        match &schema_kind.schema_kind {
-            openapiv3::SchemaKind::Type(type_) => {
-                let type_ = match type_ {
-                    openapiv3::Type::String(_string_type) => MCPToolPropertyType::String,
-                    openapiv3::Type::Number(_number_type) => MCPToolPropertyType::Number,
-                    openapiv3::Type::Integer(_integer_type) => {
-                        // TODO: should be special type?
-                        MCPToolPropertyType::Number
-                    }
-                    openapiv3::Type::Object(object_type) => {
-                        let mut object = BTreeMap::new();
-                        for (name, schema) in object_type.properties.iter() {
-                            let schema = resolve_boxed_schema(openapi, schema).unwrap();
-                            let value = schema_kind_to_mcp_tool_property(&schema, openapi);
-                            if let Some(value) = value {
-                                object.insert(name.clone(), value);
-                            }
-                        }
-                        MCPToolPropertyType::Object(object)
-                    }
-                    openapiv3::Type::Array(array_type) => {
-                        let schema =
-                            resolve_boxed_schema(openapi, array_type.items.as_ref().unwrap())
-                                .unwrap();
-                        let value = schema_kind_to_mcp_tool_property(&schema, openapi).unwrap();
-                        MCPToolPropertyType::Array(Box::new(value))
-                    }
-                    openapiv3::Type::Boolean(_boolean_type) => MCPToolPropertyType::Boolean,
-                };
+    let mut encoding_used = None;
+    if let Some(body) = &operation.request_body {
+        let body = resolve_request_body(openapi, body)?;
 
-                let required_fields: HashSet<String> = match &schema_kind.schema_kind {
-                    openapiv3::SchemaKind::Any(any_schema) => {
-                        HashSet::from_iter(any_schema.required.clone())
-                    }
-                    _ => Default::default(),
-                };
+        // prefer JSON, then the other encodings in the order of `BodyEncoding`
+        let media_type = body
+            .content
+            .iter()
+            .map(|(name, media_type)| (body_encoding(name), name, media_type))
+            .min_by_key(|(encoding, _, _)| *encoding);
 
-                let required = if let Some(default) = &schema_kind.schema_data.default {
-                    MCPToolPropertyRequired::Default(default.clone())
-                } else if required_fields
-                    .contains(&schema_kind.schema_data.title.clone().unwrap_or_default())
-                {
-                    MCPToolPropertyRequired::Required
-                } else {
-                    MCPToolPropertyRequired::Optional
-                };
+        if let Some((encoding, content_type, media_type)) = media_type {
+            // bodies without a (usable) schema accept anything
+            let mut value = media_type
+                .schema
+                .as_ref()
+                .and_then(|schema| schemas.schema_ref_to_mcp_tool_property(schema))
+                .unwrap_or_else(|| MCPToolProperty {
+                    name: String::new(),
+                    description: None,
+                    required: MCPToolPropertyRequired::Optional,
+                    type_: MCPToolPropertyType::Any,
+                    constraints: Default::default(),
+                    nullable: false,
+                });
 
-                Some(MCPToolProperty {
-                    name: schema_kind.schema_data.title.clone().unwrap_or_default(),
-                    description: schema_kind.schema_data.description.clone(),
-                    required,
-                    type_,
-                })
+            match encoding {
+                BodyEncoding::Multipart => file_parts(&mut value.type_),
+                BodyEncoding::Text => value.type_ = MCPToolPropertyType::String,
+                BodyEncoding::Binary => value.type_ = MCPToolPropertyType::File,
+                BodyEncoding::Json | BodyEncoding::FormUrlEncoded => {}
             }
-            // openapiv3::SchemaKind::OneOf { one_of } => todo!(),
-            // openapiv3::SchemaKind::AllOf { all_of } => todo!(),
-            // openapiv3::SchemaKind::AllOf { all_of } => {
-            //     let mut object = HashMap::new();
-            //     for schema in all_of.iter() {
-            //         let schema = resolve_schema(openapi, schema).unwrap();
-            //         if let Some(value) = &schema_kind_to_mcp_tool_property_type(&schema.schema_kind, openapi) {
-            //             object.insert(schema.schema_data.title.clone(), value);
-            //         }
-            //     }
-            //     Some(MCPToolPropertyType::Object(object))
-            // },
-            // openapiv3::SchemaKind::AnyOf { any_of } => todo!(),
-            // openapiv3::SchemaKind::Not { not } => todo!(),
-            // openapiv3::SchemaKind::Any(any_schema) => todo!(),
-            // _ => todo!(),
-            a => {
-                log::error!("skipping schema_kind: {:#?}", a);
-                None
+            // `fetch` sets the multipart content type itself, including the boundary
+            if encoding != BodyEncoding::Multipart {
+                headers.insert(
+                    "Content-Type".into(),
+                    ValueSource::Fixed(Value::String(body_content_type(content_type, encoding))),
+                );
             }
-        }
-    }
-
-    let mut has_body = false;
-    operation.request_body.as_ref().map(|body| {
-        let body = resolve_request_body(openapi, &body).unwrap();
 
-        // TODO: support non-json body
-        if let Some(media_type) = &body.content.get("application/json") {
-            headers.insert(
-                "Content-Type".into(),
-                ValueSource::Fixed(Value::String("application/json".into())),
-            );
-            let schema = match &media_type.schema {
-                Some(schema) => resolve_schema(openapi, schema).unwrap(),
-                None => todo!(),
-            };
-
-            let value = schema_kind_to_mcp_tool_property(&schema, openapi);
-            if let Some(mut value) = value {
-                value.name = "body".to_string();
-                properties.push(value);
-                has_body = true;
+            value.name = "body".to_string();
+            value.description = body.description.clone().or(value.description);
+            if body.required {
+                value.required = MCPToolPropertyRequired::Required;
             }
+            properties.push(value);
+            encoding_used = Some(encoding);
         }
-    });
+    }
 
     Ok(Some(MCPTool {
         call: Call {
@@ -370,124 +566,103 @@ fn operation_to_tool(
             path_params,
             headers,
             query,
-            body: has_body.then(|| ValueSource::Property(PropertyId::from_body("body"))),
+            cookies,
+            body: encoding_used.map(|encoding| Body {
+                value: ValueSource::Property(PropertyId::from_body("body")),
+                encoding,
+            }),
+            base_url: None,
         },
         properties,
+        definitions: schemas.into_definitions(),
         name: tool_name,
+        original_name,
         description,
     }))
 }
 
-fn get_oauth2_info(openapi: &OpenAPI) -> Option<&OAuth2Flows> {
+fn get_oauth2_info(openapi: &OpenAPI) -> Option<OAuth2Flows> {
     get_security_schemes(openapi)
-        .iter()
-        .find_map(|security_scheme| match security_scheme {
+        .into_iter()
+        .find_map(|security_scheme| match security_scheme.into_owned() {
             SecurityScheme::OAuth2 { flows, .. } => Some(flows),
             _ => None,
         })
 }
 
-fn get_security_schemes(openapi: &OpenAPI) -> Vec<&SecurityScheme> {
+fn get_security_schemes(openapi: &OpenAPI) -> Vec<Cow<'_, SecurityScheme>> {
     let components = openapi.components.as_ref();
     if let Some(components) = components {
         return components
             .security_schemes
             .iter()
-            .filter_map(|(_, scheme_ref)| resolve_security_scheme(openapi, scheme_ref))
+            .filter_map(|(name, scheme_ref)| {
+                resolve_security_scheme(openapi, scheme_ref)
+                    .map_err(|e| log::warn!("skipping security scheme {}: {}", name, e))
+                    .ok()
+            })
             .collect();
     }
     Vec::new()
 }
 
-fn resolve_parameter<'a>(
-    openapi: &'a OpenAPI,
-    param_ref: &'a ReferenceOr<Parameter>,
-) -> Option<&'a Parameter> {
-    match param_ref {
-        ReferenceOr::Reference { reference } => {
-            let ref_path = reference.split("/").last().unwrap();
-            let params = openapi.components.as_ref()?.parameters.get(ref_path)?;
-            resolve_parameter(openapi, params)
-        }
-        ReferenceOr::Item(param) => Some(param),
+fn body_encoding(media_type: &str) -> BodyEncoding {
+    let essence = media_type
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase();
+    match essence.as_str() {
+        "application/json" => BodyEncoding::Json,
+        _ if essence.ends_with("+json") => BodyEncoding::Json,
+        "application/x-www-form-urlencoded" => BodyEncoding::FormUrlEncoded,
+        "multipart/form-data" => BodyEncoding::Multipart,
+        "application/xml" => BodyEncoding::Text,
+        _ if essence.starts_with("text/") || essence.ends_with("+xml") => BodyEncoding::Text,
+        _ => BodyEncoding::Binary,
     }
 }
 
-fn resolve_path<'a>(
-    openapi: &'a OpenAPI,
-    path_ref: &'a ReferenceOr<PathItem>,
-) -> Option<&'a PathItem> {
-    match path_ref {
-        ReferenceOr::Reference { reference } => {
-            let ref_path = reference.split("/").last().unwrap();
-            let path = openapi.paths.paths.get(ref_path)?;
-            resolve_path(openapi, path)
-        }
-        ReferenceOr::Item(path) => Some(path),
-    }
-}
-
-fn resolve_request_body<'a>(
-    openapi: &'a OpenAPI,
-    request_body_ref: &'a ReferenceOr<RequestBody>,
-) -> Option<&'a RequestBody> {
-    match request_body_ref {
-        ReferenceOr::Reference { reference } => {
-            let ref_path = reference.split("/").last().unwrap();
-            let path = openapi.components.as_ref()?.request_bodies.get(ref_path)?;
-            resolve_request_body(openapi, path)
-        }
-        ReferenceOr::Item(request_body) => Some(request_body),
+/// The `Content-Type` header for a body, media type ranges like `image/*` can't be sent as is.
+fn body_content_type(media_type: &str, encoding: BodyEncoding) -> String {
+    match encoding {
+        _ if !media_type.contains('*') => media_type.to_string(),
+        BodyEncoding::Text => "text/plain".to_string(),
+        _ => "application/octet-stream".to_string(),
     }
 }
 
-fn resolve_schema<'a>(
-    openapi: &'a OpenAPI,
-    schema_ref: &'a ReferenceOr<Schema>,
-) -> Option<&'a Schema> {
-    match schema_ref {
-        ReferenceOr::Reference { reference } => {
-            let ref_path = reference.split("/").last().unwrap();
-            let path = openapi.components.as_ref()?.schemas.get(ref_path)?;
-            resolve_schema(openapi, path)
-        }
-        ReferenceOr::Item(schema) => Some(schema),
+/// Multipart properties with binary contents are files, on their own or in an array.
+fn file_parts(type_: &mut MCPToolPropertyType) {
+    fn is_binary(property: &MCPToolProperty) -> bool {
+        matches!(property.type_, MCPToolPropertyType::String)
+            && property.constraints.format.as_deref() == Some("binary")
     }
-}
 
-fn resolve_security_scheme<'a>(
-    openapi: &'a OpenAPI,
-    schema_ref: &'a ReferenceOr<SecurityScheme>,
-) -> Option<&'a SecurityScheme> {
-    match schema_ref {
-        ReferenceOr::Reference { reference } => {
-            let ref_path = reference.split("/").last().unwrap();
-            let path = openapi
-                .components
-                .as_ref()?
-                .security_schemes
-                .get(ref_path)?;
-            resolve_security_scheme(openapi, path)
+    if let MCPToolPropertyType::Object(object) = type_ {
+        for property in object.values_mut() {
+            if is_binary(property) {
+                property.type_ = MCPToolPropertyType::File;
+            } else if let MCPToolPropertyType::Array(items) = &mut property.type_
+                && is_binary(items)
+            {
+                items.type_ = MCPToolPropertyType::File;
+            }
         }
-        ReferenceOr::Item(schema) => Some(schema),
     }
 }
 
-fn resolve_boxed_schema<'a>(
-    openapi: &'a OpenAPI,
-    schema_ref: &'a ReferenceOr<Box<Schema>>,
-) -> Option<&'a Schema> {
-    match schema_ref {
-        ReferenceOr::Reference { reference } => {
-            let ref_path = reference.split("/").last().unwrap();
-            let path = openapi.components.as_ref()?.schemas.get(ref_path)?;
-            resolve_schema(openapi, path)
-        }
-        ReferenceOr::Item(schema) => Some(schema),
+fn json_to_value(value: &serde_json::Value) -> Option<Value> {
+    match value {
+        serde_json::Value::String(value) => Some(Value::String(value.clone())),
+        serde_json::Value::Number(value) => value.as_f64().map(Value::Number),
+        serde_json::Value::Bool(value) => Some(Value::Boolean(*value)),
+        _ => None,
     }
 }
 
-fn cleanup_string(s: &str) -> String {
+pub(super) fn cleanup_string(s: &str) -> String {
     s.chars()
         .filter_map(|c| {
             if matches!(c, '-' | '/' | '\\' | ',' | '.') {
@@ -505,6 +680,7 @@ fn cleanup_string(s: &str) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::mcp_server::test_specs::openapi_with_paths;
 
     /// Create a minimal OpenAPI spec for testing
     fn minimal_openapi() -> OpenAPI {
@@ -590,6 +766,251 @@ mod tests {
         assert!(result.unwrap().is_none()); // Tool should be skipped
     }
 
+    #[test]
+    fn test_long_tool_names_are_shortened_uniquely() {
+        let openapi = openapi_with_paths(serde_json::json!({
+            "/users": { "get": { "responses": {} } },
+            "/users/{userIdentifier}": { "get": { "responses": {} } }
+        }));
+        let options = ConverterOptions {
+            max_tool_name_length: Some(20),
+            shorten_long_tool_names: true,
+            ..Default::default()
+        };
+
+        let server = openapi_to_mcp_server(openapi, options).unwrap();
+
+        assert_eq!(server.tools[0].name, "get_users");
+        assert_eq!(server.tools[0].original_name, None);
+        let shortened = &server.tools[1];
+        assert_ne!(shortened.name, "get_users");
+        assert!(shortened.name.starts_with("get_users_"));
+        assert!(shortened.name.len() <= 20);
+        assert_eq!(
+            shortened.original_name.as_deref(),
+            Some("get_users_user_identifier")
+        );
+    }
+
+    #[test]
+    fn test_colliding_tool_names_get_a_suffix() {
+        let openapi = openapi_with_paths(serde_json::json!({
+            "/users/{id}": { "get": { "responses": {} } },
+            "/users/id": { "get": { "responses": {} } },
+            "/users-id": { "get": { "responses": {} } },
+            "/users/id_2": { "get": { "responses": {} } }
+        }));
+
+        let server = openapi_to_mcp_server(openapi, ConverterOptions::default()).unwrap();
+
+        let names: Vec<_> = server.tools.iter().map(|tool| tool.name.as_str()).collect();
+        assert_eq!(
+            names,
+            [
+                "get_users_id",
+                "get_users_id_3",
+                "get_users_id_4",
+                "get_users_id_2"
+            ]
+        );
+        assert_eq!(server.tools[1].original_name, None);
+    }
+
+    #[test]
+    fn test_non_latin_operation_ids_are_named_after_their_path() {
+        let openapi = openapi_with_paths(serde_json::json!({
+            "/users": { "get": { "operationId": "получитьПользователей", "responses": {} } },
+            "/用户": { "get": { "operationId": "用户列表", "responses": {} } }
+        }));
+        let options = ConverterOptions {
+            tool_naming: ToolNaming::OperationId,
+            client_profile: ClientProfile::Strict,
+            ..Default::default()
+        };
+
+        let server = openapi_to_mcp_server(openapi, options).unwrap();
+
+        assert_eq!(server.tools[0].name, "get_users");
+        assert!(server.tools[1].name.starts_with("get_"));
+        assert!(ClientProfile::Strict.accepts(&server.tools[1].name));
+    }
+
+    #[test]
+    fn test_strict_profile_lowers_max_length() {
+        let openapi = minimal_openapi();
+        let operation = minimal_operation();
+        let path = format!("/{}", "a".repeat(70));
+
+        let options = ConverterOptions {
+            skip_long_tool_names: true,
+            ..Default::default()
+        };
+        let tool = operation_to_tool(Method::GET, &path, &operation, &[], &openapi, &options);
+        assert!(tool.unwrap().is_some());
+
+        let options = ConverterOptions {
+            client_profile: ClientProfile::Strict,
+            skip_long_tool_names: true,
+            ..Default::default()
+        };
+        let tool = operation_to_tool(Method::GET, &path, &operation, &[], &openapi, &options);
+        assert!(tool.unwrap().is_none());
+    }
+
+    #[test]
+    fn test_head_and_options_operations_but_not_trace() {
+        let openapi = openapi_with_paths(serde_json::json!({
+            "/objects/{key}": {
+                "head": { "responses": {} },
+                "options": { "responses": {} },
+                "trace": { "responses": {} },
+                "get": { "responses": {} }
+            }
+        }));
+        let options = ConverterOptions {
+            include_methods: vec![Method::HEAD, Method::OPTIONS, Method::TRACE],
+            ..Default::default()
+        };
+
+        let server = openapi_to_mcp_server(openapi, options).unwrap();
+
+        let names: Vec<_> = server.tools.iter().map(|tool| tool.name.as_str()).collect();
+        assert_eq!(names, ["head_objects_key", "options_objects_key"]);
+        assert_eq!(server.tools[0].call.method, Method::HEAD);
+    }
+
+    #[test]
+    fn test_methods_are_filtered_per_operation() {
+        let openapi = openapi_with_paths(serde_json::json!({
+            "/users": {
+                "get": { "responses": {} },
+                "post": { "responses": {} },
+                "delete": { "responses": {} }
+            },
+            "/groups": {
+                "get": { "responses": {} }
+            }
+        }));
+        let options = ConverterOptions {
+            include_methods: vec![Method::POST, Method::DELETE],
+            ..Default::default()
+        };
+
+        let server = openapi_to_mcp_server(openapi, options).unwrap();
+
+        let names: Vec<_> = server.tools.iter().map(|tool| tool.name.as_str()).collect();
+        assert_eq!(names, ["post_users", "delete_users"]);
+    }
+
+    #[test]
+    fn test_operation_filters() {
+        let openapi = openapi_with_paths(serde_json::json!({
+            "/messages": {
+                "get": { "tags": ["mail"], "operationId": "listMessages", "responses": {} },
+                "post": { "tags": ["mail"], "operationId": "sendMessage", "responses": {} }
+            },
+            "/messages/legacy": {
+                "get": { "tags": ["mail"], "deprecated": true, "responses": {} }
+            },
+            "/events": {
+                "get": { "tags": ["calendar"], "operationId": "listEvents", "responses": {} }
+            }
+        }));
+        let options = ConverterOptions {
+            include_operations: Some("tag:mail OR operationId:*Events".parse().unwrap()),
+            exclude_operations: Some("deprecated OR method:post".parse().unwrap()),
+            ..Default::default()
+        };
+
+        let server = openapi_to_mcp_server(openapi, options).unwrap();
+
+        let names: Vec<_> = server.tools.iter().map(|tool| tool.name.as_str()).collect();
+        assert_eq!(names, ["get_messages", "get_events"]);
+    }
+
+    #[test]
+    fn test_multiple_servers() {
+        let mut openapi = openapi_with_paths(serde_json::json!({
+            "/users": { "get": { "responses": {} } },
+            "/files": {
+                "servers": [{ "url": "https://files.example.com" }],
+                "get": { "responses": {} },
+                "post": {
+                    "servers": [{
+                        "url": "https://{bucket}.upload.{region}.example.com",
+                        "variables": {
+                            "bucket": { "default": "uploads" },
+                            "region": { "default": "us", "enum": ["us", "eu"] }
+                        }
+                    }],
+                    "responses": {}
+                }
+            },
+            "/archives": {
+                "servers": [{
+                    "url": "https://{region}.archive.example.com",
+                    "variables": { "region": { "default": "north", "enum": ["north", "south"] } }
+                }],
+                "get": { "responses": {} }
+            }
+        }));
+        openapi.servers = serde_json::from_value(serde_json::json!([
+            {
+                "url": "https://{region}.example.com/v1",
+                "description": "Production",
+                "variables": {
+                    "region": { "default": "us", "enum": ["us", "eu"] }
+                }
+            },
+            { "url": "https://staging.example.com/v1", "description": "Staging" }
+        ]))
+        .unwrap();
+
+        let server = openapi_to_mcp_server(openapi.clone(), ConverterOptions::default()).unwrap();
+        assert_eq!(server.servers.len(), 2);
+        assert_eq!(server.base_url, "https://{region}.example.com/v1");
+        assert_eq!(server.base_url_variables["region"].default, "us");
+        assert_eq!(server.base_url_variables["region"].values, ["us", "eu"]);
+
+        let base_urls: Vec<_> = server
+            .tools
+            .iter()
+            .map(|tool| tool.call.base_url.as_deref())
+            .collect();
+        assert_eq!(
+            base_urls,
+            [
+                None,
+                Some("https://files.example.com"),
+                Some("https://{bucket}.upload.{region}.example.com"),
+                Some("https://{region_2}.archive.example.com")
+            ]
+        );
+        assert_eq!(server.base_url_variables["bucket"].default, "uploads");
+        // a variable that differs from the one of the API server isn't shared with it
+        assert_eq!(server.base_url_variables["region"].default, "us");
+        assert_eq!(server.base_url_variables["region_2"].default, "north");
+        assert_eq!(
+            server.base_url_variables["region_2"].values,
+            ["north", "south"]
+        );
+
+        let choose = |server: &str| {
+            let options = ConverterOptions {
+                server: Some(server.to_string()),
+                ..Default::default()
+            };
+            openapi_to_mcp_server(openapi.clone(), options).map(|server| server.base_url)
+        };
+        assert_eq!(choose("1").unwrap(), "https://staging.example.com/v1");
+        assert_eq!(choose("staging").unwrap(), "https://staging.example.com/v1");
+        assert_eq!(
+            choose("http://localhost:8080").unwrap(),
+            "http://localhost:8080"
+        );
+        assert!(choose("2").is_err());
+    }
+
     #[test]
     fn test_tool_name_at_exact_limit() {
         let openapi = minimal_openapi();
@@ -631,4 +1052,385 @@ mod tests {
     fn test_default_max_tool_name_length_constant() {
         assert_eq!(DEFAULT_MAX_TOOL_NAME_LENGTH, 80);
     }
+
+    #[test]
+    fn test_parameter_types_come_from_schema() {
+        let openapi = minimal_openapi();
+        let operation: openapiv3::Operation = serde_json::from_value(serde_json::json!({
+            "parameters": [
+                { "name": "limit", "in": "query", "schema": { "type": "integer" } },
+                { "name": "ids", "in": "query", "schema": { "type": "array", "items": { "type": "string" } } },
+                { "name": "verbose", "in": "query", "required": true, "schema": { "type": "boolean" } }
+            ],
+            "responses": {}
+        }))
+        .unwrap();
+        let options = ConverterOptions::default();
+
+        let tool = operation_to_tool(Method::GET, "/users", &operation, &[], &openapi, &options)
+            .unwrap()
+            .unwrap();
+
+        assert!(matches!(
+            tool.properties[0].type_,
+            MCPToolPropertyType::Integer
+        ));
+        assert!(matches!(
+            tool.properties[0].required,
+            MCPToolPropertyRequired::Optional
+        ));
+        assert!(matches!(
+            tool.properties[1].type_,
+            MCPToolPropertyType::Array(_)
+        ));
+        assert!(matches!(
+            tool.properties[2].type_,
+            MCPToolPropertyType::Boolean
+        ));
+        assert!(matches!(
+            tool.properties[2].required,
+            MCPToolPropertyRequired::Required
+        ));
+    }
+
+    #[test]
+    fn test_cookie_parameters() {
+        let openapi = minimal_openapi();
+        let operation: openapiv3::Operation = serde_json::from_value(serde_json::json!({
+            "parameters": [
+                { "name": "session-id", "in": "cookie", "required": true, "schema": { "type": "string" } }
+            ],
+            "responses": {}
+        }))
+        .unwrap();
+        let options = ConverterOptions::default();
+
+        let tool = operation_to_tool(Method::GET, "/users", &operation, &[], &openapi, &options)
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(tool.properties[0].name, "session_id");
+        assert!(matches!(
+            &tool.call.cookies["session-id"],
+            ValueSource::Property(property) if property.to_string() == "session_id"
+        ));
+    }
+
+    #[test]
+    fn test_single_value_parameters_are_fixed() {
+        let openapi = minimal_openapi();
+        let operation: openapiv3::Operation = serde_json::from_value(serde_json::json!({
+            "parameters": [
+                { "name": "version", "in": "query", "schema": { "type": "string", "enum": ["v2"] } },
+                { "name": "order", "in": "query", "schema": { "type": "string", "enum": ["asc", "desc"] } }
+            ],
+            "responses": {}
+        }))
+        .unwrap();
+        let options = ConverterOptions::default();
+
+        let tool = operation_to_tool(Method::GET, "/users", &operation, &[], &openapi, &options)
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(tool.properties.len(), 1);
+        assert!(matches!(
+            &tool.properties[0].type_,
+            MCPToolPropertyType::Enum(values) if values.len() == 2
+        ));
+        assert!(matches!(
+            &tool.call.query["version"].value,
+            ValueSource::Fixed(Value::String(value)) if value == "v2"
+        ));
+    }
+
+    #[test]
+    fn test_single_value_body_properties_default_to_it() {
+        let openapi = minimal_openapi();
+        let operation: openapiv3::Operation = serde_json::from_value(serde_json::json!({
+            "requestBody": {
+                "required": true,
+                "content": {
+                    "application/json": {
+                        "schema": {
+                            "type": "object",
+                            "required": ["order"],
+                            "properties": {
+                                "order": {
+                                    "type": "object",
+                                    "required": ["kind", "item"],
+                                    "properties": {
+                                        "kind": { "type": "string", "enum": ["order"] },
+                                        "item": { "type": "string" }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            },
+            "responses": {}
+        }))
+        .unwrap();
+        let options = ConverterOptions::default();
+
+        let tool = operation_to_tool(Method::POST, "/orders", &operation, &[], &openapi, &options)
+            .unwrap()
+            .unwrap();
+
+        let MCPToolPropertyType::Object(body) = &tool.properties[0].type_ else {
+            panic!("expected object, got {:?}", tool.properties[0].type_);
+        };
+        let MCPToolPropertyType::Object(order) = &body["order"].type_ else {
+            panic!("expected object, got {:?}", body["order"].type_);
+        };
+        assert!(matches!(
+            &order["kind"].required,
+            MCPToolPropertyRequired::Default(value) if value == "order"
+        ));
+        assert!(matches!(
+            order["item"].required,
+            MCPToolPropertyRequired::Required
+        ));
+    }
+
+    #[test]
+    fn test_required_body_properties() {
+        let openapi = minimal_openapi();
+        let operation: openapiv3::Operation = serde_json::from_value(serde_json::json!({
+            "requestBody": {
+                "required": true,
+                "content": {
+                    "application/json": {
+                        "schema": {
+                            "type": "object",
+                            "required": ["name"],
+                            "properties": {
+                                "name": { "type": "string", "title": "Name" },
+                                "nickname": { "type": "string" }
+                            }
+                        }
+                    }
+                }
+            },
+            "responses": {}
+        }))
+        .unwrap();
+        let options = ConverterOptions::default();
+
+        let tool = operation_to_tool(Method::POST, "/users", &operation, &[], &openapi, &options)
+            .unwrap()
+            .unwrap();
+
+        let body = &tool.properties[0];
+        assert!(matches!(body.required, MCPToolPropertyRequired::Required));
+        let MCPToolPropertyType::Object(object) = &body.type_ else {
+            panic!("expected object, got {:?}", body.type_);
+        };
+        assert!(matches!(
+            object["name"].required,
+            MCPToolPropertyRequired::Required
+        ));
+        assert!(matches!(
+            object["nickname"].required,
+            MCPToolPropertyRequired::Optional
+        ));
+    }
+
+    #[test]
+    fn test_multipart_body_with_files() {
+        let openapi = minimal_openapi();
+        let operation: openapiv3::Operation = serde_json::from_value(serde_json::json!({
+            "requestBody": {
+                "content": {
+                    "multipart/form-data": {
+                        "schema": {
+                            "type": "object",
+                            "properties": {
+                                "file": { "type": "string", "format": "binary" },
+                                "attachments": {
+                                    "type": "array",
+                                    "items": { "type": "string", "format": "binary" }
+                                },
+                                "title": { "type": "string" }
+                            }
+                        }
+                    },
+                    "text/plain": { "schema": { "type": "string" } }
+                }
+            },
+            "responses": {}
+        }))
+        .unwrap();
+        let options = ConverterOptions::default();
+
+        let tool = operation_to_tool(Method::POST, "/files", &operation, &[], &openapi, &options)
+            .unwrap()
+            .unwrap();
+
+        let body = tool.call.body.as_ref().unwrap();
+        assert_eq!(body.encoding, BodyEncoding::Multipart);
+        assert!(!tool.call.headers.contains_key("Content-Type"));
+        let MCPToolPropertyType::Object(object) = &tool.properties[0].type_ else {
+            panic!("expected object, got {:?}", tool.properties[0].type_);
+        };
+        assert!(matches!(object["file"].type_, MCPToolPropertyType::File));
+        assert!(matches!(
+            &object["attachments"].type_,
+            MCPToolPropertyType::Array(items) if matches!(items.type_, MCPToolPropertyType::File)
+        ));
+        assert!(matches!(object["title"].type_, MCPToolPropertyType::String));
+    }
+
+    #[test]
+    fn test_form_and_binary_bodies() {
+        let openapi = minimal_openapi();
+        let form: openapiv3::Operation = serde_json::from_value(serde_json::json!({
+            "requestBody": {
+                "content": {
+                    "application/x-www-form-urlencoded": {
+                        "schema": {
+                            "type": "object",
+                            "properties": { "grant_type": { "type": "string" } }
+                        }
+                    }
+                }
+            },
+            "responses": {}
+        }))
+        .unwrap();
+        let binary: openapiv3::Operation = serde_json::from_value(serde_json::json!({
+            "requestBody": { "content": { "image/*": {} } },
+            "responses": {}
+        }))
+        .unwrap();
+        let options = ConverterOptions::default();
+
+        let tool = operation_to_tool(Method::POST, "/token", &form, &[], &openapi, &options)
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            tool.call.body.as_ref().unwrap().encoding,
+            BodyEncoding::FormUrlEncoded
+        );
+        assert!(matches!(
+            &tool.call.headers["Content-Type"],
+            ValueSource::Fixed(Value::String(value)) if value == "application/x-www-form-urlencoded"
+        ));
+
+        let tool = operation_to_tool(Method::PUT, "/avatar", &binary, &[], &openapi, &options)
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            tool.call.body.as_ref().unwrap().encoding,
+            BodyEncoding::Binary
+        );
+        assert!(matches!(
+            tool.properties[0].type_,
+            MCPToolPropertyType::File
+        ));
+        assert!(matches!(
+            &tool.call.headers["Content-Type"],
+            ValueSource::Fixed(Value::String(value)) if value == "application/octet-stream"
+        ));
+    }
+
+    #[test]
+    fn test_array_and_schemaless_bodies() {
+        let openapi = minimal_openapi();
+        let bulk: openapiv3::Operation = serde_json::from_value(serde_json::json!({
+            "requestBody": {
+                "required": true,
+                "content": {
+                    "application/json": {
+                        "schema": {
+                            "type": "array",
+                            "items": {
+                                "type": "object",
+                                "properties": { "name": { "type": "string" } }
+                            }
+                        }
+                    }
+                }
+            },
+            "responses": {}
+        }))
+        .unwrap();
+        let schemaless: openapiv3::Operation = serde_json::from_value(serde_json::json!({
+            "requestBody": {
+                "description": "Anything goes",
+                "content": { "application/json": {} }
+            },
+            "responses": {}
+        }))
+        .unwrap();
+        let options = ConverterOptions::default();
+
+        let tool = operation_to_tool(Method::POST, "/users", &bulk, &[], &openapi, &options)
+            .unwrap()
+            .unwrap();
+        assert_eq!(tool.properties[0].name, "body");
+        assert!(matches!(
+            tool.properties[0].required,
+            MCPToolPropertyRequired::Required
+        ));
+        assert!(matches!(
+            &tool.properties[0].type_,
+            MCPToolPropertyType::Array(items) if matches!(items.type_, MCPToolPropertyType::Object(_))
+        ));
+
+        let tool = operation_to_tool(Method::POST, "/users", &schemaless, &[], &openapi, &options)
+            .unwrap()
+            .unwrap();
+        assert_eq!(tool.properties[0].name, "body");
+        assert_eq!(
+            tool.properties[0].description.as_deref(),
+            Some("Anything goes")
+        );
+        assert!(matches!(tool.properties[0].type_, MCPToolPropertyType::Any));
+        assert!(tool.call.body.is_some());
+    }
+
+    #[test]
+    fn test_parameter_styles() {
+        let openapi = minimal_openapi();
+        let operation: openapiv3::Operation = serde_json::from_value(serde_json::json!({
+            "parameters": [
+                { "name": "id", "in": "path", "required": true, "style": "matrix", "schema": { "type": "string" } },
+                { "name": "filter", "in": "query", "style": "deepObject", "explode": true, "schema": { "type": "object" } },
+                { "name": "tags", "in": "query", "explode": false, "schema": { "type": "array", "items": { "type": "string" } } },
+                { "name": "page", "in": "query", "schema": { "type": "integer" } }
+            ],
+            "responses": {}
+        }))
+        .unwrap();
+        let options = ConverterOptions::default();
+
+        let tool = operation_to_tool(
+            Method::GET,
+            "/items/{id}",
+            &operation,
+            &[],
+            &openapi,
+            &options,
+        )
+        .unwrap()
+        .unwrap();
+
+        let id = &tool.call.path_params["id"];
+        assert_eq!(id.style, ParameterStyle::Matrix);
+        assert!(!id.explode);
+        let filter = &tool.call.query["filter"];
+        assert_eq!(filter.style, ParameterStyle::DeepObject);
+        assert!(filter.explode);
+        // a free-form object keeps its keys, so they can be serialized as `filter[key]`
+        let filter = tool.properties.iter().find(|p| p.name == "filter").unwrap();
+        assert!(matches!(filter.type_, MCPToolPropertyType::Map(_)));
+        let tags = &tool.call.query["tags"];
+        assert_eq!(tags.style, ParameterStyle::Form);
+        assert!(!tags.explode);
+        let page = &tool.call.query["page"];
+        assert_eq!(page.style, ParameterStyle::Form);
+        assert!(page.explode);
+    }
 }
@@ -0,0 +1,293 @@
+//! Expressions selecting the operations tools are generated for.
+//!
+//! An expression combines conditions with `AND`, `OR`, `NOT` and parentheses, e.g.
+//! `tag:mail AND method:GET AND NOT deprecated`. The conditions are:
+//!
+//! - `tag:<glob>`: one of the tags of the operation matches, ignoring case,
+//! - `operationId:<glob>`: the `operationId` matches,
+//! - `path:<regex>`: the regex matches the path,
+//! - `method:<method>`: the operation has the method,
+//! - `deprecated`: the operation is deprecated.
+//!
+//! Globs support `*` and `?`. Values with spaces or parentheses are quoted, e.g.
+//! `tag:"Mail Messages"` or `path:"^/users/(me|\{id\})"`.
+
+use std::{fmt, str::FromStr};
+
+use http::Method;
+use openapiv3::Operation;
+use regex::Regex;
+
+#[derive(Debug, Clone)]
+pub enum OperationFilter {
+    Tag(Regex),
+    OperationId(Regex),
+    Path(Regex),
+    Method(Method),
+    Deprecated,
+    And(Box<OperationFilter>, Box<OperationFilter>),
+    Or(Box<OperationFilter>, Box<OperationFilter>),
+    Not(Box<OperationFilter>),
+}
+
+impl OperationFilter {
+    pub fn matches(&self, method: &Method, path: &str, operation: &Operation) -> bool {
+        match self {
+            OperationFilter::Tag(glob) => operation.tags.iter().any(|tag| glob.is_match(tag)),
+            OperationFilter::OperationId(glob) => operation
+                .operation_id
+                .as_deref()
+                .is_some_and(|operation_id| glob.is_match(operation_id)),
+            OperationFilter::Path(regex) => regex.is_match(path),
+            OperationFilter::Method(expected) => method == expected,
+            OperationFilter::Deprecated => operation.deprecated,
+            OperationFilter::And(left, right) => {
+                left.matches(method, path, operation) && right.matches(method, path, operation)
+            }
+            OperationFilter::Or(left, right) => {
+                left.matches(method, path, operation) || right.matches(method, path, operation)
+            }
+            OperationFilter::Not(filter) => !filter.matches(method, path, operation),
+        }
+    }
+}
+
+impl FromStr for OperationFilter {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let tokens = tokenize(s)?;
+        let mut parser = Parser {
+            tokens,
+            position: 0,
+        };
+        let filter = parser.or()?;
+        match parser.tokens.get(parser.position) {
+            None => Ok(filter),
+            Some(token) => Err(format!("unexpected {token} in filter {s}")),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    Open,
+    Close,
+    /// A condition or keyword, `quoted` words are never keywords.
+    Word {
+        value: String,
+        quoted: bool,
+    },
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Token::Open => write!(f, "("),
+            Token::Close => write!(f, ")"),
+            Token::Word { value, .. } => write!(f, "{value}"),
+        }
+    }
+}
+
+fn tokenize(s: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = Vec::new();
+    let mut chars = s.chars().peekable();
+    while let Some(&c) = chars.peek() {
+        match c {
+            _ if c.is_whitespace() => {
+                chars.next();
+            }
+            '(' => {
+                chars.next();
+                tokens.push(Token::Open);
+            }
+            ')' => {
+                chars.next();
+                tokens.push(Token::Close);
+            }
+            _ => {
+                let mut value = String::new();
+                let mut quoted = false;
+                while let Some(&c) = chars.peek() {
+                    if c.is_whitespace() || c == '(' || c == ')' {
+                        break;
+                    }
+                    chars.next();
+                    if c == '"' {
+                        quoted = true;
+                        loop {
+                            match chars.next() {
+                                Some('"') => break,
+                                Some(c) => value.push(c),
+                                None => return Err(format!("unterminated quote in filter {s}")),
+                            }
+                        }
+                    } else {
+                        value.push(c);
+                    }
+                }
+                tokens.push(Token::Word { value, quoted });
+            }
+        }
+    }
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    position: usize,
+}
+
+impl Parser {
+    fn or(&mut self) -> Result<OperationFilter, String> {
+        let mut filter = self.and()?;
+        while self.keyword("OR") {
+            filter = OperationFilter::Or(Box::new(filter), Box::new(self.and()?));
+        }
+        Ok(filter)
+    }
+
+    fn and(&mut self) -> Result<OperationFilter, String> {
+        let mut filter = self.not()?;
+        while self.keyword("AND") {
+            filter = OperationFilter::And(Box::new(filter), Box::new(self.not()?));
+        }
+        Ok(filter)
+    }
+
+    fn not(&mut self) -> Result<OperationFilter, String> {
+        if self.keyword("NOT") {
+            return Ok(OperationFilter::Not(Box::new(self.not()?)));
+        }
+        self.condition()
+    }
+
+    fn condition(&mut self) -> Result<OperationFilter, String> {
+        let token = self.tokens.get(self.position).cloned();
+        self.position += 1;
+        match token {
+            Some(Token::Open) => {
+                let filter = self.or()?;
+                match self.tokens.get(self.position) {
+                    Some(Token::Close) => {
+                        self.position += 1;
+                        Ok(filter)
+                    }
+                    _ => Err("missing ) in filter".to_string()),
+                }
+            }
+            Some(Token::Word { value, .. }) => condition(&value),
+            Some(Token::Close) => Err("unexpected ) in filter".to_string()),
+            None => Err("filter ends where a condition is expected".to_string()),
+        }
+    }
+
+    /// Consumes the next token if it is the (case insensitive) `keyword`.
+    fn keyword(&mut self, keyword: &str) -> bool {
+        match self.tokens.get(self.position) {
+            Some(Token::Word {
+                value,
+                quoted: false,
+            }) if value.eq_ignore_ascii_case(keyword) => {
+                self.position += 1;
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
+fn condition(condition: &str) -> Result<OperationFilter, String> {
+    if condition.eq_ignore_ascii_case("deprecated") {
+        return Ok(OperationFilter::Deprecated);
+    }
+
+    let Some((key, value)) = condition.split_once(':') else {
+        return Err(format!(
+            "unknown condition {condition}, expected tag:, operationId:, path:, method: or deprecated"
+        ));
+    };
+    match key {
+        "tag" => glob(value, true).map(OperationFilter::Tag),
+        "operationId" => glob(value, false).map(OperationFilter::OperationId),
+        "path" => Regex::new(value)
+            .map(OperationFilter::Path)
+            .map_err(|e| e.to_string()),
+        "method" => Method::from_bytes(value.to_uppercase().as_bytes())
+            .map(OperationFilter::Method)
+            .map_err(|e| e.to_string()),
+        _ => Err(format!(
+            "unknown condition {key}:, expected tag:, operationId:, path:, method: or deprecated"
+        )),
+    }
+}
+
+/// Translates a glob with `*` and `?` wildcards into a regex matching whole strings.
+fn glob(glob: &str, ignore_case: bool) -> Result<Regex, String> {
+    let pattern = regex::escape(glob).replace("\\*", ".*").replace("\\?", ".");
+    let flags = if ignore_case { "(?i)" } else { "" };
+    Regex::new(&format!("{flags}^{pattern}$")).map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn operation(operation_id: &str, tags: &[&str], deprecated: bool) -> Operation {
+        Operation {
+            operation_id: Some(operation_id.to_string()),
+            tags: tags.iter().map(|tag| tag.to_string()).collect(),
+            deprecated,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_combined_conditions() {
+        let filter: OperationFilter = "tag:mail AND method:get AND NOT deprecated"
+            .parse()
+            .unwrap();
+
+        let list = operation("listMessages", &["Mail"], false);
+        let old = operation("listMessagesV1", &["mail"], true);
+        let calendar = operation("listEvents", &["calendar"], false);
+        assert!(filter.matches(&Method::GET, "/messages", &list));
+        assert!(!filter.matches(&Method::POST, "/messages", &list));
+        assert!(!filter.matches(&Method::GET, "/messages", &old));
+        assert!(!filter.matches(&Method::GET, "/events", &calendar));
+    }
+
+    #[test]
+    fn test_precedence_and_parentheses() {
+        let list = operation("listMessages", &["mail"], false);
+        let matches = |filter: &str, path| {
+            let filter: OperationFilter = filter.parse().unwrap();
+            filter.matches(&Method::GET, path, &list)
+        };
+
+        assert!(matches(
+            "tag:calendar AND deprecated OR operationId:list*",
+            "/"
+        ));
+        assert!(!matches(
+            "tag:calendar AND (deprecated OR operationId:list*)",
+            "/"
+        ));
+        assert!(matches("NOT NOT operationId:list?essages", "/"));
+        assert!(matches(
+            r#"path:"^/users/(me|\{id\})/messages$""#,
+            "/users/me/messages"
+        ));
+        assert!(matches(r#"tag:"Mail" or tag:"Mail Messages""#, "/"));
+    }
+
+    #[test]
+    fn test_invalid_filters() {
+        assert!("tag:mail AND".parse::<OperationFilter>().is_err());
+        assert!("(tag:mail".parse::<OperationFilter>().is_err());
+        assert!("tag:mail)".parse::<OperationFilter>().is_err());
+        assert!("label:mail".parse::<OperationFilter>().is_err());
+        assert!("path:\"(\"".parse::<OperationFilter>().is_err());
+    }
+}
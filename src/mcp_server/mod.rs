@@ -7,8 +7,14 @@ use http::Method;
 use openapiv3::{AuthorizationCodeOAuth2Flow, OpenAPI};
 
 pub use converter::ConverterOptions;
+pub use filter::OperationFilter;
+pub use naming::{ClientProfile, ToolNameCase, ToolNaming};
 
 mod converter;
+mod filter;
+mod naming;
+mod reference;
+mod schema;
 
 #[derive(Debug, Clone)]
 pub struct MCPServer {
@@ -16,16 +22,41 @@ pub struct MCPServer {
     pub tools: Vec<MCPTool>,
     pub version: String,
     pub description: Option<String>,
+    /// URL of the server the tools call, with `{variable}` placeholders for `base_url_variables`.
     pub base_url: String,
+    /// Variables of `base_url` and of the tools' [`Call::base_url`].
+    pub base_url_variables: BTreeMap<String, ServerVariable>,
+    /// All the servers of the API, including the one of `base_url`.
+    pub servers: Vec<ApiServer>,
     pub oauth2_info: Option<AuthorizationCodeOAuth2Flow>,
 }
 
+#[derive(Debug, Clone)]
+pub struct ApiServer {
+    pub url: String,
+    pub description: Option<String>,
+    pub variables: BTreeMap<String, ServerVariable>,
+}
+
+/// A `{variable}` placeholder of a server URL.
+#[derive(Debug, Clone)]
+pub struct ServerVariable {
+    pub default: String,
+    /// Values the variable is limited to, any value is allowed if empty.
+    pub values: Vec<String>,
+    pub description: Option<String>,
+}
+
 #[derive(Debug, Clone)]
 pub struct MCPTool {
     pub name: String,
+    /// Name the tool had before it was shortened to fit the maximum length.
+    pub original_name: Option<String>,
     pub description: String,
     // pub required: bool,
     pub properties: Vec<MCPToolProperty>,
+    /// Recursive schemas used by the properties, keyed by the name they are referenced with.
+    pub definitions: BTreeMap<String, MCPToolProperty>,
     pub call: Call,
 }
 
@@ -35,6 +66,28 @@ pub struct MCPToolProperty {
     pub description: Option<String>,
     pub required: MCPToolPropertyRequired,
     pub type_: MCPToolPropertyType,
+    pub constraints: MCPToolPropertyConstraints,
+    /// Whether `null` is accepted in addition to the values of the type.
+    pub nullable: bool,
+}
+
+/// Validation keywords of the schema, each one only applies to the types it is defined for.
+#[derive(Debug, Clone, Default)]
+pub struct MCPToolPropertyConstraints {
+    pub minimum: Option<f64>,
+    pub exclusive_minimum: bool,
+    pub maximum: Option<f64>,
+    pub exclusive_maximum: bool,
+    pub multiple_of: Option<f64>,
+    pub min_length: Option<usize>,
+    pub max_length: Option<usize>,
+    pub pattern: Option<String>,
+    pub min_items: Option<usize>,
+    pub max_items: Option<usize>,
+    pub unique_items: bool,
+    pub format: Option<String>,
+    /// Whether an object keeps the properties it doesn't list.
+    pub additional_properties: bool,
 }
 
 #[derive(Debug, Clone)]
@@ -57,9 +110,29 @@ impl Display for PropertyId {
 pub enum MCPToolPropertyType {
     String,
     Number,
+    Integer,
     Boolean,
     Array(Box<MCPToolProperty>),
     Object(BTreeMap<String, MCPToolProperty>),
+    /// An object with arbitrary keys whose values all have the same type (`additionalProperties`).
+    Map(Box<MCPToolProperty>),
+    /// A value matching at least one of the variants (`oneOf`/`anyOf`, validated like `anyOf` since
+    /// `z.union` accepts the first variant that matches). When `discriminator` is set, every
+    /// variant is an object that pins that property to a literal value.
+    Union {
+        variants: Vec<MCPToolProperty>,
+        discriminator: Option<String>,
+    },
+    /// All of the parts at once (`allOf` that can't be merged into a single object).
+    Intersection(Vec<MCPToolProperty>),
+    Literal(serde_json::Value),
+    /// One of the values (`enum`), there are always at least two of them.
+    Enum(Vec<serde_json::Value>),
+    Any,
+    /// A recursive schema, defined in [`MCPTool::definitions`].
+    Reference(String),
+    /// Contents of a file, given either base64 encoded or as an MCP resource.
+    File,
 }
 
 #[derive(Debug, Clone)]
@@ -67,9 +140,50 @@ pub struct Call {
     pub method: Method,
     pub headers: BTreeMap<String, ValueSource>,
     pub path: String,
-    pub path_params: BTreeMap<String, ValueSource>,
-    pub query: BTreeMap<String, ValueSource>,
-    pub body: Option<ValueSource>,
+    pub path_params: BTreeMap<String, ParameterValue>,
+    pub query: BTreeMap<String, ParameterValue>,
+    pub cookies: BTreeMap<String, ValueSource>,
+    pub body: Option<Body>,
+    /// Server the operation is called on instead of [`MCPServer::base_url`], with `{variable}`
+    /// placeholders for [`MCPServer::base_url_variables`].
+    pub base_url: Option<String>,
+}
+
+/// A path or query parameter along with how its value is serialized.
+#[derive(Debug, Clone)]
+pub struct ParameterValue {
+    pub value: ValueSource,
+    pub style: ParameterStyle,
+    pub explode: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParameterStyle {
+    // Query
+    Form,
+    SpaceDelimited,
+    PipeDelimited,
+    DeepObject,
+    // Path
+    Simple,
+    Label,
+    Matrix,
+}
+
+#[derive(Debug, Clone)]
+pub struct Body {
+    pub value: ValueSource,
+    pub encoding: BodyEncoding,
+}
+
+/// How the body is sent, picked from the media types the operation accepts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum BodyEncoding {
+    Json,
+    FormUrlEncoded,
+    Multipart,
+    Text,
+    Binary,
 }
 
 #[derive(Debug, Clone)]
@@ -108,6 +222,31 @@ impl Display for Value {
     }
 }
 
+impl ParameterValue {
+    /// `explode` defaults to true for the `form` style only.
+    pub fn new(value: ValueSource, style: ParameterStyle, explode: Option<bool>) -> Self {
+        Self {
+            value,
+            style,
+            explode: explode.unwrap_or(style == ParameterStyle::Form),
+        }
+    }
+}
+
+impl ParameterStyle {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ParameterStyle::Form => "form",
+            ParameterStyle::SpaceDelimited => "spaceDelimited",
+            ParameterStyle::PipeDelimited => "pipeDelimited",
+            ParameterStyle::DeepObject => "deepObject",
+            ParameterStyle::Simple => "simple",
+            ParameterStyle::Label => "label",
+            ParameterStyle::Matrix => "matrix",
+        }
+    }
+}
+
 impl MCPServer {
     pub fn from_openapi(openapi: OpenAPI, options: ConverterOptions) -> anyhow::Result<Self> {
         converter::openapi_to_mcp_server(openapi, options)
@@ -130,12 +269,42 @@ impl PropertyId {
         Self(path.to_string())
     }
 
-    // pub fn from_cookie(cookie: &str) -> Self {
-    //     Self(format!("cookie-{}", cookie))
-    // }
+    pub fn from_cookie(cookie: &str) -> Self {
+        // Self(format!("cookie-{}", cookie))
+        Self(cookie.to_string())
+    }
 
     pub fn from_body(body: &str) -> Self {
         // Self(format!("path-{}", path))
         Self(body.to_string())
     }
 }
+
+/// Specs the tests of the conversion are built from.
+#[cfg(test)]
+mod test_specs {
+    use openapiv3::OpenAPI;
+    use serde_json::{Value, json};
+
+    /// An OpenAPI spec with the given `paths`.
+    pub(super) fn openapi_with_paths(paths: Value) -> OpenAPI {
+        openapi_with(json!({ "paths": paths }))
+    }
+
+    /// An OpenAPI spec without paths, with the given component `schemas`.
+    pub(super) fn openapi_with_schemas(schemas: Value) -> OpenAPI {
+        openapi_with(json!({ "components": { "schemas": schemas } }))
+    }
+
+    fn openapi_with(fields: Value) -> OpenAPI {
+        let mut spec = json!({
+            "openapi": "3.0.0",
+            "info": { "title": "Test API", "version": "1.0.0" },
+            "paths": {}
+        });
+        if let (Some(spec), Value::Object(fields)) = (spec.as_object_mut(), fields) {
+            spec.extend(fields);
+        }
+        serde_json::from_value(spec).unwrap()
+    }
+}
@@ -0,0 +1,671 @@
+//! Naming of the tools generated for operations.
+
+use std::{
+    borrow::Cow,
+    collections::{HashMap, HashSet},
+    fmt,
+    str::FromStr,
+};
+
+use convert_case::{Case, Casing};
+use http::Method;
+use openapiv3::Operation;
+
+use crate::mcp_server::MCPTool;
+use crate::mcp_server::converter::cleanup_string;
+
+/// How the name of a tool is derived from its operation.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum ToolNaming {
+    /// The method and the path, e.g. `get_users_user_id`.
+    #[default]
+    Path,
+    /// The `operationId`, e.g. `getUser`.
+    OperationId,
+    /// The first tag and the `operationId`, e.g. `users_getUser`.
+    TagOperationId,
+    /// A template with `{method}`, `{path}`, `{operationId}` and `{tag}` placeholders.
+    Template(String),
+}
+
+/// Restrictions of the clients the tool names have to satisfy.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ClientProfile {
+    /// The character set of the MCP specification, `[A-Za-z0-9_-]`.
+    #[default]
+    Mcp,
+    /// The MCP character set and at most 64 characters, the limit of several clients.
+    Strict,
+}
+
+/// Case the tool names are converted to.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ToolNameCase {
+    #[default]
+    Snake,
+    Camel,
+    Pascal,
+    Kebab,
+    /// Keep the case of the `operationId` and tag.
+    Preserve,
+}
+
+impl ToolNaming {
+    fn template(&self) -> &str {
+        match self {
+            ToolNaming::Path => "{method}_{path}",
+            ToolNaming::OperationId => "{operationId}",
+            ToolNaming::TagOperationId => "{tag}_{operationId}",
+            ToolNaming::Template(template) => template,
+        }
+    }
+}
+
+impl FromStr for ToolNaming {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "path" => Ok(ToolNaming::Path),
+            "operation-id" => Ok(ToolNaming::OperationId),
+            "tag-operation-id" => Ok(ToolNaming::TagOperationId),
+            _ if s.contains('{') => Ok(ToolNaming::Template(s.to_string())),
+            _ => Err(format!(
+                "unknown tool naming {s}, expected path, operation-id, tag-operation-id or a template like {{tag}}_{{operationId}}"
+            )),
+        }
+    }
+}
+
+impl ClientProfile {
+    /// Maximum length of the tool names, if the profile limits it.
+    pub fn max_tool_name_length(&self) -> Option<usize> {
+        match self {
+            ClientProfile::Mcp => None,
+            ClientProfile::Strict => Some(64),
+        }
+    }
+
+    /// Whether `name` can be used as tool name.
+    pub fn accepts(&self, name: &str) -> bool {
+        !name.is_empty()
+            && name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-'))
+            && self
+                .max_tool_name_length()
+                .is_none_or(|max_length| name.len() <= max_length)
+    }
+}
+
+impl FromStr for ClientProfile {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "mcp" => Ok(ClientProfile::Mcp),
+            "strict" => Ok(ClientProfile::Strict),
+            _ => Err(format!(
+                "unknown client profile {s}, expected mcp or strict"
+            )),
+        }
+    }
+}
+
+impl FromStr for ToolNameCase {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "snake" => Ok(ToolNameCase::Snake),
+            "camel" => Ok(ToolNameCase::Camel),
+            "pascal" => Ok(ToolNameCase::Pascal),
+            "kebab" => Ok(ToolNameCase::Kebab),
+            "preserve" => Ok(ToolNameCase::Preserve),
+            _ => Err(format!(
+                "unknown tool name case {s}, expected snake, camel, pascal, kebab or preserve"
+            )),
+        }
+    }
+}
+
+impl fmt::Display for ToolNaming {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.template())
+    }
+}
+
+/// Names the tool of an operation. Operations without the `operationId` or tag the naming asks
+/// for, or whose name has no ASCII letter or digit left once cleaned up (e.g. a Cyrillic
+/// `operationId`), are named after their path instead. A path whose words are all non-Latin
+/// leaves nothing in the name, so a hash of it is appended to tell the operations apart.
+pub fn tool_name(
+    naming: &ToolNaming,
+    case: ToolNameCase,
+    method: &Method,
+    path: &str,
+    operation: &Operation,
+) -> String {
+    let rendered = render(naming.template(), method, path, operation);
+    let name = rendered.as_deref().map(|name| clean_name(name, case));
+    let (template, name) = match name.filter(|name| is_usable(name)) {
+        Some(name) => (naming.template(), name),
+        None => {
+            match rendered {
+                None => log::info!(
+                    "{} {} has no value for every placeholder of {}, naming it after its path",
+                    method,
+                    path,
+                    naming
+                ),
+                Some(rendered) => log::warn!(
+                    "{} {} would be named {}, which has no ASCII letter or digit, naming it after its path",
+                    method,
+                    path,
+                    rendered
+                ),
+            }
+            let template = ToolNaming::Path.template();
+            let name = render(template, method, path, operation).unwrap_or_default();
+            (template, clean_name(&name, case))
+        }
+    };
+
+    let path_words = cleanup_string(path);
+    if template.contains("{path}")
+        && path_words.chars().any(char::is_alphanumeric)
+        && !is_usable(&clean_name(&path_words, case))
+    {
+        let hash = format!("{:08x}", fnv1a(path));
+        return with_suffix(&name, &hash, case, usize::MAX);
+    }
+    name
+}
+
+/// Restricts `name` to `[A-Za-z0-9_-]`, which is all MCP allows, and converts it to `case`.
+/// Separators other than `_` and `-` only split words and accented letters lose their accents.
+fn clean_name(name: &str, case: ToolNameCase) -> String {
+    let name: String = name
+        .chars()
+        .filter_map(|c| match c {
+            '_' | '-' => Some(Cow::Owned(c.to_string())),
+            _ if c.is_ascii_alphanumeric() => Some(Cow::Owned(c.to_string())),
+            _ if c.is_whitespace() || matches!(c, '/' | '\\' | ',' | '.' | ':') => {
+                Some(Cow::Borrowed("_"))
+            }
+            _ => transliterate(c).map(Cow::Borrowed),
+        })
+        .collect();
+
+    match case {
+        ToolNameCase::Snake => name.to_case(Case::Snake),
+        ToolNameCase::Camel => name.to_case(Case::Camel),
+        ToolNameCase::Pascal => name.to_case(Case::Pascal),
+        ToolNameCase::Kebab => name.to_case(Case::Kebab),
+        ToolNameCase::Preserve => name,
+    }
+}
+
+/// Whether a cleaned up name says anything, i.e. isn't empty or only separators.
+fn is_usable(name: &str) -> bool {
+    name.chars().any(|c| c.is_ascii_alphanumeric())
+}
+
+/// ASCII spelling of the Latin letters with diacritics, other characters are dropped.
+fn transliterate(c: char) -> Option<&'static str> {
+    let ascii = match c {
+        'À'..='Å' | 'Ā' | 'Ă' | 'Ą' => "A",
+        'à'..='å' | 'ā' | 'ă' | 'ą' => "a",
+        'Æ' => "AE",
+        'æ' => "ae",
+        'Ç' | 'Ć' | 'Ĉ' | 'Ċ' | 'Č' => "C",
+        'ç' | 'ć' | 'ĉ' | 'ċ' | 'č' => "c",
+        'Ð' | 'Ď' | 'Đ' => "D",
+        'ð' | 'ď' | 'đ' => "d",
+        'È'..='Ë' | 'Ē' | 'Ĕ' | 'Ė' | 'Ę' | 'Ě' => "E",
+        'è'..='ë' | 'ē' | 'ĕ' | 'ė' | 'ę' | 'ě' => "e",
+        'Ĝ' | 'Ğ' | 'Ġ' | 'Ģ' => "G",
+        'ĝ' | 'ğ' | 'ġ' | 'ģ' => "g",
+        'Ĥ' | 'Ħ' => "H",
+        'ĥ' | 'ħ' => "h",
+        'Ì'..='Ï' | 'Ĩ' | 'Ī' | 'Ĭ' | 'Į' | 'İ' => "I",
+        'ì'..='ï' | 'ĩ' | 'ī' | 'ĭ' | 'į' | 'ı' => "i",
+        'Ĵ' => "J",
+        'ĵ' => "j",
+        'Ķ' => "K",
+        'ķ' => "k",
+        'Ĺ' | 'Ļ' | 'Ľ' | 'Ŀ' | 'Ł' => "L",
+        'ĺ' | 'ļ' | 'ľ' | 'ŀ' | 'ł' => "l",
+        'Ñ' | 'Ń' | 'Ņ' | 'Ň' => "N",
+        'ñ' | 'ń' | 'ņ' | 'ň' => "n",
+        'Ò'..='Ö' | 'Ø' | 'Ō' | 'Ŏ' | 'Ő' => "O",
+        'ò'..='ö' | 'ø' | 'ō' | 'ŏ' | 'ő' => "o",
+        'Œ' => "OE",
+        'œ' => "oe",
+        'Ŕ' | 'Ŗ' | 'Ř' => "R",
+        'ŕ' | 'ŗ' | 'ř' => "r",
+        'Ś' | 'Ŝ' | 'Ş' | 'Š' => "S",
+        'ś' | 'ŝ' | 'ş' | 'š' => "s",
+        'ß' => "ss",
+        'Ţ' | 'Ť' | 'Ŧ' => "T",
+        'ţ' | 'ť' | 'ŧ' => "t",
+        'Þ' => "TH",
+        'þ' => "th",
+        'Ù'..='Ü' | 'Ũ' | 'Ū' | 'Ŭ' | 'Ů' | 'Ű' | 'Ų' => "U",
+        'ù'..='ü' | 'ũ' | 'ū' | 'ŭ' | 'ů' | 'ű' | 'ų' => "u",
+        'Ŵ' => "W",
+        'ŵ' => "w",
+        'Ý' | 'Ŷ' | 'Ÿ' => "Y",
+        'ý' | 'ÿ' | 'ŷ' => "y",
+        'Ź' | 'Ż' | 'Ž' => "Z",
+        'ź' | 'ż' | 'ž' => "z",
+        _ => return None,
+    };
+    Some(ascii)
+}
+
+/// Shortens the name of a tool to at most `max_length` bytes, deterministically:
+///
+/// 1. path parameter segments are dropped, e.g. `/users/{userId}/messages` becomes
+///    `/users/messages`,
+/// 2. common long words are abbreviated, e.g. `configuration` becomes `config`,
+/// 3. when the name is still too long, it is truncated and a hash of the full name is appended.
+///
+/// Shortened names can collide with other names, [`disambiguate_shortened_names`] resolves that.
+pub fn shorten_tool_name(
+    naming: &ToolNaming,
+    case: ToolNameCase,
+    method: &Method,
+    path: &str,
+    operation: &Operation,
+    max_length: usize,
+) -> String {
+    let full_name = tool_name(naming, case, method, path, operation);
+
+    let path_without_parameters = path
+        .split('/')
+        .filter(|segment| !segment.contains('{'))
+        .collect::<Vec<_>>()
+        .join("/");
+    let name = tool_name(naming, case, method, &path_without_parameters, operation);
+    if name.len() <= max_length {
+        return name;
+    }
+
+    let name = abbreviate(&name);
+    if name.len() <= max_length {
+        return name;
+    }
+
+    with_hash(&name, &full_name, case, max_length)
+}
+
+/// Appends a hash of the original name to the shortened tools whose name is also used by another
+/// tool, so every name is unique again.
+pub fn disambiguate_shortened_names(tools: &mut [MCPTool], case: ToolNameCase, max_length: usize) {
+    let mut counts = HashMap::new();
+    for tool in tools.iter() {
+        *counts.entry(collision_key(&tool.name)).or_insert(0) += 1;
+    }
+
+    for tool in tools {
+        if let Some(original_name) = &tool.original_name
+            && counts[&collision_key(&tool.name)] > 1
+        {
+            tool.name = with_hash(&tool.name, original_name, case, max_length);
+        }
+    }
+}
+
+/// Makes the names of the tools unique. The first tool with a name keeps it, the following ones
+/// get a `_2`, `_3`, ... suffix (in the separator of `case`), skipping names used by other tools.
+///
+/// Names that only differ in case or in `-` and `_` collide too, since the tools are written to
+/// files and imported as modules under their name.
+pub fn deduplicate_tool_names(tools: &mut [MCPTool], case: ToolNameCase, max_length: usize) {
+    let mut used: HashSet<_> = tools.iter().map(|tool| collision_key(&tool.name)).collect();
+    let mut first_tools = HashMap::new();
+
+    for index in 0..tools.len() {
+        let key = collision_key(&tools[index].name);
+        let Some(&first) = first_tools.get(&key) else {
+            first_tools.insert(key, index);
+            continue;
+        };
+
+        let name = (2..)
+            .map(|count| with_suffix(&tools[index].name, &count.to_string(), case, max_length))
+            .find(|name| !used.contains(&collision_key(name)))
+            .expect("a free suffix");
+        log::warn!(
+            "{} {} and {} {} are both named {}, naming the latter {}",
+            tools[first].call.method,
+            tools[first].call.path,
+            tools[index].call.method,
+            tools[index].call.path,
+            tools[index].name,
+            name
+        );
+
+        used.insert(collision_key(&name));
+        first_tools.insert(collision_key(&name), index);
+        tools[index].name = name;
+    }
+}
+
+/// What two names must not share to be told apart, as file and module names.
+fn collision_key(name: &str) -> String {
+    name.to_lowercase().replace('-', "_")
+}
+
+/// Abbreviations of words that are common in API paths and operation ids.
+const ABBREVIATIONS: &[(&str, &str)] = &[
+    ("administrator", "admin"),
+    ("application", "app"),
+    ("attachment", "attach"),
+    ("authentication", "auth"),
+    ("authorization", "authz"),
+    ("configuration", "config"),
+    ("definition", "def"),
+    ("delete", "del"),
+    ("description", "desc"),
+    ("directory", "dir"),
+    ("environment", "env"),
+    ("identifier", "id"),
+    ("information", "info"),
+    ("management", "mgmt"),
+    ("message", "msg"),
+    ("notification", "notif"),
+    ("organization", "org"),
+    ("parameter", "param"),
+    ("permission", "perm"),
+    ("reference", "ref"),
+    ("repository", "repo"),
+    ("request", "req"),
+    ("response", "resp"),
+    ("schedule", "sched"),
+    ("specification", "spec"),
+    ("statistic", "stat"),
+    ("subscription", "sub"),
+    ("template", "tmpl"),
+    ("transaction", "txn"),
+    ("version", "ver"),
+];
+
+/// Abbreviates the words of a name in any of the [`ToolNameCase`]s, keeping the case of their
+/// first letter.
+fn abbreviate(name: &str) -> String {
+    let mut abbreviated = String::with_capacity(name.len());
+    let mut word = String::new();
+    let mut previous = None;
+    for c in name.chars() {
+        let boundary = matches!(c, '_' | '-')
+            || (c.is_uppercase()
+                && previous.is_some_and(|p: char| p.is_lowercase() || p.is_numeric()));
+        if boundary {
+            abbreviated.push_str(&abbreviate_word(&word));
+            word.clear();
+        }
+        if matches!(c, '_' | '-') {
+            abbreviated.push(c);
+        } else {
+            word.push(c);
+        }
+        previous = Some(c);
+    }
+    abbreviated.push_str(&abbreviate_word(&word));
+    abbreviated
+}
+
+fn abbreviate_word(word: &str) -> String {
+    let lowercase = word.to_lowercase();
+    let (singular, plural) = match lowercase.strip_suffix('s') {
+        Some(singular) if !ABBREVIATIONS.iter().any(|(long, _)| *long == lowercase) => {
+            (singular, "s")
+        }
+        _ => (lowercase.as_str(), ""),
+    };
+    let Some((_, short)) = ABBREVIATIONS.iter().find(|(long, _)| *long == singular) else {
+        return word.to_string();
+    };
+
+    let mut abbreviation = String::new();
+    if word.starts_with(char::is_uppercase) {
+        abbreviation.extend(short[..1].chars().flat_map(char::to_uppercase));
+        abbreviation.push_str(&short[1..]);
+    } else {
+        abbreviation.push_str(short);
+    }
+    abbreviation.push_str(plural);
+    abbreviation
+}
+
+/// Truncates `name` to make room for a hash of `original_name` within `max_length`.
+fn with_hash(name: &str, original_name: &str, case: ToolNameCase, max_length: usize) -> String {
+    let hash = format!("{:06x}", fnv1a(original_name) & 0xff_ffff);
+    with_suffix(name, &hash, case, max_length)
+}
+
+/// Truncates `name` to make room for `suffix` within `max_length`.
+fn with_suffix(name: &str, suffix: &str, case: ToolNameCase, max_length: usize) -> String {
+    let separator = match case {
+        ToolNameCase::Kebab => "-",
+        ToolNameCase::Camel | ToolNameCase::Pascal => "",
+        ToolNameCase::Snake | ToolNameCase::Preserve => "_",
+    };
+
+    let mut prefix_length = max_length.saturating_sub(separator.len() + suffix.len());
+    while !name.is_char_boundary(prefix_length.min(name.len())) {
+        prefix_length -= 1;
+    }
+    let prefix = name[..prefix_length.min(name.len())].trim_end_matches(['_', '-']);
+    format!("{prefix}{separator}{suffix}")
+}
+
+/// 32 bit FNV-1a, which unlike [`std::hash::DefaultHasher`] is guaranteed to be stable.
+fn fnv1a(value: &str) -> u32 {
+    value.bytes().fold(0x811c_9dc5, |hash, byte| {
+        (hash ^ u32::from(byte)).wrapping_mul(0x0100_0193)
+    })
+}
+
+/// Fills in the placeholders of `template`, `None` if the operation lacks one of them.
+fn render(template: &str, method: &Method, path: &str, operation: &Operation) -> Option<String> {
+    let mut name = template
+        .replace("{method}", &method.as_str().to_lowercase())
+        .replace("{path}", &cleanup_string(path));
+    if name.contains("{operationId}") {
+        name = name.replace("{operationId}", operation.operation_id.as_deref()?);
+    }
+    if name.contains("{tag}") {
+        name = name.replace("{tag}", operation.tags.first()?);
+    }
+    Some(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn operation(operation_id: Option<&str>, tags: &[&str]) -> Operation {
+        Operation {
+            operation_id: operation_id.map(str::to_string),
+            tags: tags.iter().map(|tag| tag.to_string()).collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_path_naming() {
+        let name = tool_name(
+            &ToolNaming::Path,
+            ToolNameCase::Snake,
+            &Method::GET,
+            "/users/{userId}/mailFolders",
+            &operation(Some("listMailFolders"), &[]),
+        );
+        assert_eq!(name, "get_users_user_id_mail_folders");
+    }
+
+    #[test]
+    fn test_operation_id_naming() {
+        let operation = operation(Some("listMessages"), &["Mail Messages"]);
+
+        let name = |naming: &ToolNaming, case| {
+            tool_name(naming, case, &Method::GET, "/messages", &operation)
+        };
+
+        assert_eq!(
+            name(&ToolNaming::OperationId, ToolNameCase::Snake),
+            "list_messages"
+        );
+        assert_eq!(
+            name(&ToolNaming::OperationId, ToolNameCase::Preserve),
+            "listMessages"
+        );
+        assert_eq!(
+            name(&ToolNaming::TagOperationId, ToolNameCase::Camel),
+            "mailMessagesListMessages"
+        );
+        assert_eq!(
+            name(
+                &ToolNaming::Template("{tag}.{method}".to_string()),
+                ToolNameCase::Kebab
+            ),
+            "mail-messages-get"
+        );
+    }
+
+    #[test]
+    fn test_missing_operation_id_falls_back_to_path() {
+        let name = tool_name(
+            &ToolNaming::TagOperationId,
+            ToolNameCase::Snake,
+            &Method::POST,
+            "/messages",
+            &operation(None, &["mail"]),
+        );
+        assert_eq!(name, "post_messages");
+    }
+
+    #[test]
+    fn test_names_only_use_the_mcp_character_set() {
+        let name = tool_name(
+            &ToolNaming::OperationId,
+            ToolNameCase::Preserve,
+            &Method::GET,
+            "/cafés",
+            &operation(Some("listeDesCafés@Zürich(été)📍"), &[]),
+        );
+        assert_eq!(name, "listeDesCafesZurichete");
+        assert!(ClientProfile::Mcp.accepts(&name));
+
+        let name = tool_name(
+            &ToolNaming::Path,
+            ToolNameCase::Snake,
+            &Method::GET,
+            "/日本/{番号}/straße",
+            &operation(None, &[]),
+        );
+        assert_eq!(name, "get_strasse");
+    }
+
+    #[test]
+    fn test_non_latin_names_fall_back_to_path_or_hash() {
+        let name = tool_name(
+            &ToolNaming::OperationId,
+            ToolNameCase::Snake,
+            &Method::GET,
+            "/users",
+            &operation(Some("получитьПользователей"), &[]),
+        );
+        assert_eq!(name, "get_users");
+
+        let name = tool_name(
+            &ToolNaming::OperationId,
+            ToolNameCase::Kebab,
+            &Method::GET,
+            "/用户/{编号}",
+            &operation(Some("ユーザー一覧"), &[]),
+        );
+        assert_eq!(name, format!("get-{:08x}", fnv1a("/用户/{编号}")));
+        assert!(ClientProfile::Strict.accepts(&name));
+    }
+
+    #[test]
+    fn test_strict_profile_limits_length() {
+        assert!(ClientProfile::Mcp.accepts(&"a".repeat(80)));
+        assert!(!ClientProfile::Strict.accepts(&"a".repeat(65)));
+        assert!(!ClientProfile::Strict.accepts("café"));
+        assert!(!ClientProfile::Mcp.accepts(""));
+    }
+
+    #[test]
+    fn test_parse_naming() {
+        assert_eq!("path".parse(), Ok(ToolNaming::Path));
+        assert_eq!(
+            "{tag}_{operationId}".parse(),
+            Ok(ToolNaming::Template("{tag}_{operationId}".to_string()))
+        );
+        assert!("tag".parse::<ToolNaming>().is_err());
+        assert_eq!("camel".parse(), Ok(ToolNameCase::Camel));
+    }
+
+    #[test]
+    fn test_shorten_drops_path_parameters_first() {
+        let name = shorten_tool_name(
+            &ToolNaming::Path,
+            ToolNameCase::Snake,
+            &Method::GET,
+            "/users/{userId}/mailFolders/{mailFolderId}/messageRules",
+            &operation(None, &[]),
+            40,
+        );
+        assert_eq!(name, "get_users_mail_folders_message_rules");
+    }
+
+    #[test]
+    fn test_shorten_abbreviates_words() {
+        let operation = operation(Some("listDeviceManagementConfigurationPolicies"), &[]);
+        let name = |case| {
+            shorten_tool_name(
+                &ToolNaming::OperationId,
+                case,
+                &Method::GET,
+                "/policies",
+                &operation,
+                35,
+            )
+        };
+
+        assert_eq!(
+            name(ToolNameCase::Snake),
+            "list_device_mgmt_config_policies"
+        );
+        assert_eq!(name(ToolNameCase::Camel), "listDeviceMgmtConfigPolicies");
+    }
+
+    #[test]
+    fn test_shorten_appends_hash_when_still_too_long() {
+        let name = |path: &str| {
+            shorten_tool_name(
+                &ToolNaming::Path,
+                ToolNameCase::Snake,
+                &Method::GET,
+                path,
+                &operation(None, &[]),
+                24,
+            )
+        };
+
+        let first = name("/deviceAppManagement/mobileApps/contentVersions");
+        let second = name("/deviceAppManagement/mobileApps/contentFiles");
+        assert_eq!(first.len(), 24);
+        assert!(first.starts_with("get_device_app_mg"));
+        assert_ne!(first, second);
+        assert_eq!(
+            first,
+            name("/deviceAppManagement/mobileApps/contentVersions")
+        );
+    }
+}
@@ -0,0 +1,278 @@
+//! Resolution of `$ref`s within an OpenAPI document.
+//!
+//! References are JSON pointers into the document. The common `#/components/{kind}/{name}` form
+//! is looked up directly in [`openapiv3::Components`]; anything else is resolved by walking the
+//! JSON representation of the part of the spec the pointer points into. References to other files
+//! have to be bundled beforehand, see [`crate::spec::bundle_external_refs`].
+
+use std::borrow::Cow;
+
+use openapiv3::{
+    Components, OpenAPI, Parameter, PathItem, ReferenceOr, RequestBody, Schema, SecurityScheme,
+};
+use serde::de::DeserializeOwned;
+
+use crate::spec::pointer;
+
+/// Maximum number of references followed when a reference points to another reference.
+const MAX_REFERENCE_CHAIN: usize = 32;
+
+pub fn resolve_parameter<'a>(
+    openapi: &'a OpenAPI,
+    param_ref: &'a ReferenceOr<Parameter>,
+) -> anyhow::Result<Cow<'a, Parameter>> {
+    resolve(openapi, param_ref, "parameters", |components, name| {
+        components.parameters.get(name)
+    })
+}
+
+pub fn resolve_path<'a>(
+    openapi: &'a OpenAPI,
+    path_ref: &'a ReferenceOr<PathItem>,
+) -> anyhow::Result<Cow<'a, PathItem>> {
+    resolve(openapi, path_ref, "pathItems", |_, _| None)
+}
+
+pub fn resolve_request_body<'a>(
+    openapi: &'a OpenAPI,
+    request_body_ref: &'a ReferenceOr<RequestBody>,
+) -> anyhow::Result<Cow<'a, RequestBody>> {
+    resolve(
+        openapi,
+        request_body_ref,
+        "requestBodies",
+        |components, name| components.request_bodies.get(name),
+    )
+}
+
+pub fn resolve_schema<'a>(
+    openapi: &'a OpenAPI,
+    schema_ref: &'a ReferenceOr<Schema>,
+) -> anyhow::Result<Cow<'a, Schema>> {
+    resolve(openapi, schema_ref, "schemas", |components, name| {
+        components.schemas.get(name)
+    })
+}
+
+pub fn resolve_boxed_schema<'a>(
+    openapi: &'a OpenAPI,
+    schema_ref: &'a ReferenceOr<Box<Schema>>,
+) -> anyhow::Result<Cow<'a, Schema>> {
+    match schema_ref {
+        ReferenceOr::Reference { reference } => {
+            let schema = lookup(openapi, reference, "schemas", |components, name| {
+                components.schemas.get(name)
+            })?;
+            match schema {
+                Cow::Borrowed(schema) => resolve_schema(openapi, schema),
+                Cow::Owned(schema) => {
+                    Ok(Cow::Owned(resolve_schema(openapi, &schema)?.into_owned()))
+                }
+            }
+        }
+        ReferenceOr::Item(schema) => Ok(Cow::Borrowed(schema)),
+    }
+}
+
+pub fn resolve_security_scheme<'a>(
+    openapi: &'a OpenAPI,
+    scheme_ref: &'a ReferenceOr<SecurityScheme>,
+) -> anyhow::Result<Cow<'a, SecurityScheme>> {
+    resolve(
+        openapi,
+        scheme_ref,
+        "securitySchemes",
+        |components, name| components.security_schemes.get(name),
+    )
+}
+
+/// Follows `item` until it is no longer a reference.
+fn resolve<'a, T, F>(
+    openapi: &'a OpenAPI,
+    item: &'a ReferenceOr<T>,
+    kind: &str,
+    component: F,
+) -> anyhow::Result<Cow<'a, T>>
+where
+    T: Clone + DeserializeOwned,
+    F: Fn(&'a Components, &str) -> Option<&'a ReferenceOr<T>>,
+{
+    let mut item = Cow::Borrowed(item);
+    for _ in 0..MAX_REFERENCE_CHAIN {
+        let reference = match item {
+            Cow::Borrowed(ReferenceOr::Item(item)) => return Ok(Cow::Borrowed(item)),
+            Cow::Owned(ReferenceOr::Item(item)) => return Ok(Cow::Owned(item)),
+            Cow::Borrowed(ReferenceOr::Reference { reference }) => reference.clone(),
+            Cow::Owned(ReferenceOr::Reference { reference }) => reference,
+        };
+        item = lookup(openapi, &reference, kind, &component)?;
+    }
+    anyhow::bail!(
+        "Reference chain exceeds {} references, it is probably circular",
+        MAX_REFERENCE_CHAIN
+    )
+}
+
+/// Looks up the target of `reference`, which may itself be another reference.
+fn lookup<'a, T, F>(
+    openapi: &'a OpenAPI,
+    reference: &str,
+    kind: &str,
+    component: F,
+) -> anyhow::Result<Cow<'a, ReferenceOr<T>>>
+where
+    T: Clone + DeserializeOwned,
+    F: Fn(&'a Components, &str) -> Option<&'a ReferenceOr<T>>,
+{
+    let (document, fragment) = pointer::split_reference(reference);
+    anyhow::ensure!(
+        document.is_empty(),
+        "Reference to another document was not bundled: {}",
+        reference
+    );
+    let segments = pointer::parse_fragment(fragment)?;
+
+    if let [components, component_kind, name] = segments.as_slice()
+        && components == "components"
+        && component_kind == kind
+        && let Some(item) = openapi
+            .components
+            .as_ref()
+            .and_then(|components| component(components, name))
+    {
+        return Ok(Cow::Borrowed(item));
+    }
+
+    let value = lookup_value(openapi, &segments)?
+        .ok_or_else(|| anyhow::anyhow!("Reference not found: {}", reference))?;
+    let item = serde_json::from_value(value)
+        .map_err(|e| anyhow::anyhow!("Failed to parse referenced {}: {}", reference, e))?;
+    Ok(Cow::Owned(item))
+}
+
+/// Resolves a pointer against the JSON representation of the spec. Only the part of the spec that
+/// the pointer points into is serialized.
+fn lookup_value(
+    openapi: &OpenAPI,
+    segments: &[String],
+) -> anyhow::Result<Option<serde_json::Value>> {
+    let (value, rest) = match segments {
+        [extension, rest @ ..] if extension.starts_with("x-") => {
+            let value = openapi.extensions.get(extension);
+            return Ok(value.and_then(|value| pointer::lookup(value, rest).cloned()));
+        }
+        [paths, path, rest @ ..] if paths == "paths" => {
+            (serde_json::to_value(openapi.paths.paths.get(path))?, rest)
+        }
+        [components, kind, name, rest @ ..] if components == "components" => {
+            let Some(components) = &openapi.components else {
+                return Ok(None);
+            };
+            let value = match kind.as_str() {
+                "schemas" => serde_json::to_value(components.schemas.get(name))?,
+                "responses" => serde_json::to_value(components.responses.get(name))?,
+                "parameters" => serde_json::to_value(components.parameters.get(name))?,
+                "examples" => serde_json::to_value(components.examples.get(name))?,
+                "requestBodies" => serde_json::to_value(components.request_bodies.get(name))?,
+                "headers" => serde_json::to_value(components.headers.get(name))?,
+                "securitySchemes" => serde_json::to_value(components.security_schemes.get(name))?,
+                "links" => serde_json::to_value(components.links.get(name))?,
+                "callbacks" => serde_json::to_value(components.callbacks.get(name))?,
+                _ => return Ok(None),
+            };
+            (value, rest)
+        }
+        _ => (serde_json::to_value(openapi)?, segments),
+    };
+    Ok(pointer::lookup(&value, rest).cloned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn openapi() -> OpenAPI {
+        serde_json::from_value(serde_json::json!({
+            "openapi": "3.0.0",
+            "info": { "title": "Test API", "version": "1.0.0" },
+            "paths": {
+                "/users/{id}": {
+                    "get": {
+                        "parameters": [
+                            { "name": "id", "in": "path", "required": true, "schema": { "type": "string" } }
+                        ],
+                        "responses": {}
+                    }
+                }
+            },
+            "components": {
+                "schemas": {
+                    "User": {
+                        "type": "object",
+                        "properties": { "address": { "type": "object", "title": "Address" } }
+                    },
+                    "Alias": { "$ref": "#/components/schemas/User" },
+                    "a/b": { "type": "string", "title": "Escaped" }
+                }
+            },
+            "x-openapi2mcp-external-documents": {
+                "common.yaml": { "Error": { "type": "string", "title": "Error" } }
+            }
+        }))
+        .unwrap()
+    }
+
+    fn title(schema: &Schema) -> Option<&str> {
+        schema.schema_data.title.as_deref()
+    }
+
+    #[test]
+    fn resolve_nested_pointer() {
+        let openapi = openapi();
+        let schema_ref = ReferenceOr::ref_("#/components/schemas/User/properties/address");
+        let schema = resolve_schema(&openapi, &schema_ref).unwrap();
+        assert_eq!(title(&schema), Some("Address"));
+    }
+
+    #[test]
+    fn resolve_escaped_pointer() {
+        let openapi = openapi();
+        let schema_ref = ReferenceOr::ref_("#/components/schemas/a~1b");
+        let schema = resolve_schema(&openapi, &schema_ref).unwrap();
+        assert_eq!(title(&schema), Some("Escaped"));
+    }
+
+    #[test]
+    fn resolve_reference_chain() {
+        let openapi = openapi();
+        let schema_ref = ReferenceOr::ref_("#/components/schemas/Alias");
+        let schema = resolve_schema(&openapi, &schema_ref).unwrap();
+        assert!(matches!(
+            schema.schema_kind,
+            openapiv3::SchemaKind::Type(openapiv3::Type::Object(_))
+        ));
+    }
+
+    #[test]
+    fn resolve_bundled_document() {
+        let openapi = openapi();
+        let schema_ref = ReferenceOr::ref_("#/x-openapi2mcp-external-documents/common.yaml/Error");
+        let schema = resolve_schema(&openapi, &schema_ref).unwrap();
+        assert_eq!(title(&schema), Some("Error"));
+    }
+
+    #[test]
+    fn resolve_parameter_from_path() {
+        let openapi = openapi();
+        let param_ref = ReferenceOr::ref_("#/paths/~1users~1%7Bid%7D/get/parameters/0");
+        let parameter = resolve_parameter(&openapi, &param_ref).unwrap();
+        assert_eq!(parameter.parameter_data_ref().name, "id");
+    }
+
+    #[test]
+    fn missing_reference_is_an_error() {
+        let openapi = openapi();
+        let schema_ref = ReferenceOr::ref_("#/components/schemas/Missing");
+        assert!(resolve_schema(&openapi, &schema_ref).is_err());
+    }
+}
@@ -0,0 +1,1077 @@
+//! Conversion of OpenAPI schemas into tool properties.
+
+use std::collections::{BTreeMap, HashMap};
+
+use openapiv3::{
+    AdditionalProperties, AnySchema, Discriminator, OpenAPI, ParameterSchemaOrContent, ReferenceOr,
+    Schema, SchemaData, SchemaKind, VariantOrUnknownOrEmpty,
+};
+
+use crate::mcp_server::converter::cleanup_string;
+use crate::mcp_server::reference::{resolve_boxed_schema, resolve_schema};
+use crate::mcp_server::{
+    MCPToolProperty, MCPToolPropertyConstraints, MCPToolPropertyRequired, MCPToolPropertyType,
+};
+
+pub const DEFAULT_MAX_SCHEMA_DEPTH: u32 = 32;
+
+/// Converts the schemas of a single tool.
+///
+/// Schemas that reference themselves, directly or through other schemas, are converted once into
+/// a definition and referenced by name wherever the cycle closes, see
+/// [`MCPToolPropertyType::Reference`]. Schemas nested deeper than the maximum depth are accepted as
+/// any value.
+pub struct SchemaConverter<'a> {
+    openapi: &'a OpenAPI,
+    max_depth: usize,
+    depth: usize,
+    /// References that are currently being converted.
+    stack: Vec<String>,
+    /// Definition names of the references that turned out to be recursive.
+    recursive: HashMap<String, String>,
+    definitions: BTreeMap<String, MCPToolProperty>,
+}
+
+impl<'a> SchemaConverter<'a> {
+    pub fn new(openapi: &'a OpenAPI, max_depth: u32) -> Self {
+        Self {
+            openapi,
+            max_depth: max_depth as usize,
+            depth: 0,
+            stack: Vec::new(),
+            recursive: HashMap::new(),
+            definitions: BTreeMap::new(),
+        }
+    }
+
+    /// The definitions of the recursive schemas converted so far.
+    pub fn into_definitions(self) -> BTreeMap<String, MCPToolProperty> {
+        self.definitions
+    }
+
+    pub fn schema_ref_to_mcp_tool_property(
+        &mut self,
+        schema_ref: &ReferenceOr<Schema>,
+    ) -> Option<MCPToolProperty> {
+        let schema = resolve_schema(self.openapi, schema_ref)
+            .map_err(|e| log::warn!("skipping schema: {}", e))
+            .ok()?;
+        match schema_ref {
+            ReferenceOr::Reference { reference } => {
+                self.referenced_schema_to_mcp_tool_property(reference, &schema)
+            }
+            ReferenceOr::Item(_) => self.schema_kind_to_mcp_tool_property(&schema),
+        }
+    }
+
+    fn boxed_schema_ref_to_mcp_tool_property(
+        &mut self,
+        schema_ref: &ReferenceOr<Box<Schema>>,
+    ) -> Option<MCPToolProperty> {
+        let schema = resolve_boxed_schema(self.openapi, schema_ref)
+            .map_err(|e| log::warn!("skipping schema: {}", e))
+            .ok()?;
+        match schema_ref {
+            ReferenceOr::Reference { reference } => {
+                self.referenced_schema_to_mcp_tool_property(reference, &schema)
+            }
+            ReferenceOr::Item(_) => self.schema_kind_to_mcp_tool_property(&schema),
+        }
+    }
+
+    /// Parameters either have a `schema` or a `content` map with a single media type.
+    pub fn parameter_schema_to_mcp_tool_property(
+        &mut self,
+        format: &ParameterSchemaOrContent,
+    ) -> Option<MCPToolProperty> {
+        let schema = match format {
+            ParameterSchemaOrContent::Schema(schema) => schema,
+            ParameterSchemaOrContent::Content(content) => {
+                content.values().next()?.schema.as_ref()?
+            }
+        };
+        self.schema_ref_to_mcp_tool_property(schema)
+    }
+
+    fn referenced_schema_to_mcp_tool_property(
+        &mut self,
+        reference: &str,
+        schema: &Schema,
+    ) -> Option<MCPToolProperty> {
+        if self.stack.iter().any(|r| r == reference) {
+            let type_ = MCPToolPropertyType::Reference(self.definition_name(reference));
+            return Some(MCPToolProperty {
+                name: schema.schema_data.title.clone().unwrap_or_default(),
+                description: schema.schema_data.description.clone(),
+                required: required(schema, &type_),
+                type_,
+                constraints: Default::default(),
+                nullable: false,
+            });
+        }
+
+        self.stack.push(reference.to_string());
+        let property = self.schema_kind_to_mcp_tool_property(schema);
+        self.stack.pop();
+        let property = property?;
+
+        match self.recursive.get(reference) {
+            Some(name) => {
+                self.definitions.insert(
+                    name.clone(),
+                    MCPToolProperty {
+                        required: MCPToolPropertyRequired::Required,
+                        ..property.clone()
+                    },
+                );
+                // the definition already accepts null if the schema does
+                Some(MCPToolProperty {
+                    type_: MCPToolPropertyType::Reference(name.clone()),
+                    nullable: false,
+                    ..property
+                })
+            }
+            None => Some(property),
+        }
+    }
+
+    fn definition_name(&mut self, reference: &str) -> String {
+        if let Some(name) = self.recursive.get(reference) {
+            return name.clone();
+        }
+
+        let schema_name = reference.rsplit('/').next().unwrap_or(reference);
+        log::warn!(
+            "Schema {} is recursive, it will be generated as a lazy schema",
+            schema_name
+        );
+
+        let base_name = format!("{}_schema", cleanup_string(schema_name));
+        let mut name = base_name.clone();
+        let mut count = 1;
+        while self.recursive.values().any(|used| used == &name) {
+            count += 1;
+            name = format!("{}_{}", base_name, count);
+        }
+        self.recursive.insert(reference.to_string(), name.clone());
+        name
+    }
+
+    pub fn schema_kind_to_mcp_tool_property(&mut self, schema: &Schema) -> Option<MCPToolProperty> {
+        let type_ = if self.depth >= self.max_depth {
+            log::warn!(
+                "Schema {} is nested deeper than {} levels, accepting any value",
+                self.stack.last().map(String::as_str).unwrap_or("<inline>"),
+                self.max_depth
+            );
+            MCPToolPropertyType::Any
+        } else {
+            self.depth += 1;
+            let type_ = self.schema_to_mcp_tool_property_type(schema);
+            self.depth -= 1;
+            type_?
+        };
+
+        Some(MCPToolProperty {
+            name: schema.schema_data.title.clone().unwrap_or_default(),
+            description: schema.schema_data.description.clone(),
+            required: required(schema, &type_),
+            type_,
+            constraints: constraints(schema),
+            nullable: schema.schema_data.nullable,
+        })
+    }
+
+    fn schema_to_mcp_tool_property_type(&mut self, schema: &Schema) -> Option<MCPToolPropertyType> {
+        let type_ = match &schema.schema_kind {
+            SchemaKind::Type(type_) => match type_ {
+                openapiv3::Type::String(string_type) => {
+                    enumeration_to_mcp_tool_property_type(&string_type.enumeration)
+                        .unwrap_or(MCPToolPropertyType::String)
+                }
+                openapiv3::Type::Number(number_type) => {
+                    enumeration_to_mcp_tool_property_type(&number_type.enumeration)
+                        .unwrap_or(MCPToolPropertyType::Number)
+                }
+                openapiv3::Type::Integer(integer_type) => {
+                    enumeration_to_mcp_tool_property_type(&integer_type.enumeration)
+                        .unwrap_or(MCPToolPropertyType::Integer)
+                }
+                openapiv3::Type::Object(object_type) => {
+                    let mut type_ = self.object_to_mcp_tool_property_type(
+                        &object_type.properties,
+                        object_type.additional_properties.as_ref(),
+                    );
+                    mark_required(&mut type_, &object_type.required);
+                    type_
+                }
+                openapiv3::Type::Array(array_type) => {
+                    self.array_to_mcp_tool_property_type(array_type.items.as_ref())
+                }
+                openapiv3::Type::Boolean(boolean_type) => {
+                    enumeration_to_mcp_tool_property_type(&boolean_type.enumeration)
+                        .unwrap_or(MCPToolPropertyType::Boolean)
+                }
+            },
+            SchemaKind::OneOf { one_of } => {
+                self.union_to_mcp_tool_property_type(one_of, &schema.schema_data)?
+            }
+            SchemaKind::AnyOf { any_of } => {
+                self.union_to_mcp_tool_property_type(any_of, &schema.schema_data)?
+            }
+            SchemaKind::AllOf { all_of } => self.all_of_to_mcp_tool_property_type(all_of)?,
+            // Zod has no negation, accept anything and let the API validate it
+            SchemaKind::Not { .. } => MCPToolPropertyType::Any,
+            SchemaKind::Any(any_schema) => {
+                self.any_schema_to_mcp_tool_property_type(any_schema, &schema.schema_data)?
+            }
+        };
+        Some(type_)
+    }
+
+    /// An object without properties of its own is a map, of the `additionalProperties` schema or,
+    /// unless they are forbidden, of any value. See [`constraints`] for objects that accept
+    /// additional properties next to their own.
+    fn object_to_mcp_tool_property_type<'b>(
+        &mut self,
+        properties: impl IntoIterator<Item = (&'b String, &'b ReferenceOr<Box<Schema>>)>,
+        additional_properties: Option<&AdditionalProperties>,
+    ) -> MCPToolPropertyType {
+        let properties: Vec<_> = properties.into_iter().collect();
+        if !properties.is_empty() {
+            return self.object_properties_to_mcp_tool_property_type(properties);
+        }
+        let values = match additional_properties {
+            Some(AdditionalProperties::Schema(schema)) => {
+                self.schema_ref_to_mcp_tool_property(schema)
+            }
+            Some(AdditionalProperties::Any(false)) => {
+                return MCPToolPropertyType::Object(BTreeMap::new());
+            }
+            Some(AdditionalProperties::Any(true)) | None => None,
+        };
+        let values = values.unwrap_or_else(|| MCPToolProperty {
+            name: String::new(),
+            description: None,
+            required: MCPToolPropertyRequired::Required,
+            type_: MCPToolPropertyType::Any,
+            constraints: Default::default(),
+            nullable: false,
+        });
+        MCPToolPropertyType::Map(Box::new(MCPToolProperty {
+            required: MCPToolPropertyRequired::Required,
+            ..values
+        }))
+    }
+
+    fn object_properties_to_mcp_tool_property_type<'b>(
+        &mut self,
+        properties: impl IntoIterator<Item = (&'b String, &'b ReferenceOr<Box<Schema>>)>,
+    ) -> MCPToolPropertyType {
+        let mut object = BTreeMap::new();
+        for (name, schema) in properties {
+            // Tools only describe requests, so properties assigned by the server are left out,
+            // while `writeOnly` ones are kept
+            if resolve_boxed_schema(self.openapi, schema)
+                .is_ok_and(|schema| schema.schema_data.read_only)
+            {
+                continue;
+            }
+            let value = self.boxed_schema_ref_to_mcp_tool_property(schema);
+            if let Some(value) = value {
+                object.insert(name.clone(), value);
+            }
+        }
+        MCPToolPropertyType::Object(object)
+    }
+
+    fn array_to_mcp_tool_property_type(
+        &mut self,
+        items: Option<&ReferenceOr<Box<Schema>>>,
+    ) -> MCPToolPropertyType {
+        let items = items
+            .and_then(|items| self.boxed_schema_ref_to_mcp_tool_property(items))
+            .unwrap_or_else(|| MCPToolProperty {
+                name: String::new(),
+                description: None,
+                required: MCPToolPropertyRequired::Optional,
+                type_: MCPToolPropertyType::Any,
+                constraints: Default::default(),
+                nullable: false,
+            });
+        MCPToolPropertyType::Array(Box::new(items))
+    }
+
+    /// Converts the `oneOf`/`anyOf` variants into a union. If the schema has a discriminator,
+    /// each object variant gets its discriminator property pinned to the value that selects it,
+    /// taken from the discriminator mapping or, failing that, the name of the referenced schema.
+    fn union_to_mcp_tool_property_type(
+        &mut self,
+        variants: &[ReferenceOr<Schema>],
+        schema_data: &SchemaData,
+    ) -> Option<MCPToolPropertyType> {
+        let discriminator = schema_data.discriminator.as_ref();
+
+        let mut properties = Vec::new();
+        let mut discriminated = discriminator.is_some();
+        for variant_ref in variants {
+            let Some(mut property) = self.schema_ref_to_mcp_tool_property(variant_ref) else {
+                continue;
+            };
+            property.required = MCPToolPropertyRequired::Required;
+
+            if let Some(discriminator) = discriminator {
+                let value = match variant_ref {
+                    ReferenceOr::Reference { reference } => {
+                        discriminator_value(discriminator, reference)
+                    }
+                    ReferenceOr::Item(_) => None,
+                };
+                match (&mut property.type_, value) {
+                    (MCPToolPropertyType::Object(object), Some(value)) => {
+                        let description = object
+                            .get(&discriminator.property_name)
+                            .and_then(|property| property.description.clone());
+                        object.insert(
+                            discriminator.property_name.clone(),
+                            MCPToolProperty {
+                                name: discriminator.property_name.clone(),
+                                description,
+                                required: MCPToolPropertyRequired::Required,
+                                type_: MCPToolPropertyType::Literal(serde_json::Value::String(
+                                    value,
+                                )),
+                                constraints: Default::default(),
+                                nullable: false,
+                            },
+                        );
+                    }
+                    _ => discriminated = false,
+                }
+            }
+
+            properties.push(property);
+        }
+
+        match properties.len() {
+            0 => None,
+            1 => properties.pop().map(|property| property.type_),
+            _ => Some(MCPToolPropertyType::Union {
+                variants: properties,
+                discriminator: discriminator
+                    .filter(|_| discriminated)
+                    .map(|discriminator| discriminator.property_name.clone()),
+            }),
+        }
+    }
+
+    fn all_of_to_mcp_tool_property_type(
+        &mut self,
+        parts: &[ReferenceOr<Schema>],
+    ) -> Option<MCPToolPropertyType> {
+        // a part may list properties of another part as required
+        let required: Vec<String> = parts
+            .iter()
+            .filter_map(|part| resolve_schema(self.openapi, part).ok())
+            .flat_map(|part| required_properties(&part).to_vec())
+            .collect();
+
+        let parts = parts
+            .iter()
+            .filter_map(|part| self.schema_ref_to_mcp_tool_property(part))
+            .collect();
+        let mut type_ = merge_all_of(parts)?;
+        mark_required(&mut type_, &required);
+        Some(type_)
+    }
+
+    /// Schemas that don't fit one of the `openapiv3` kinds, e.g. an object with `allOf` next to
+    /// its own properties, or a schema without a type.
+    fn any_schema_to_mcp_tool_property_type(
+        &mut self,
+        any_schema: &AnySchema,
+        schema_data: &SchemaData,
+    ) -> Option<MCPToolPropertyType> {
+        let mut parts = Vec::new();
+
+        let base = match any_schema.typ.as_deref() {
+            _ if !any_schema.enumeration.is_empty() => {
+                enumeration_to_mcp_tool_property_type(&any_schema.enumeration)
+            }
+            Some("string") => Some(MCPToolPropertyType::String),
+            Some("number") => Some(MCPToolPropertyType::Number),
+            Some("integer") => Some(MCPToolPropertyType::Integer),
+            Some("boolean") => Some(MCPToolPropertyType::Boolean),
+            Some("array") => Some(self.array_to_mcp_tool_property_type(any_schema.items.as_ref())),
+            Some("object") => Some(self.object_to_mcp_tool_property_type(
+                &any_schema.properties,
+                any_schema.additional_properties.as_ref(),
+            )),
+            None if !any_schema.properties.is_empty() => {
+                Some(self.object_to_mcp_tool_property_type(
+                    &any_schema.properties,
+                    any_schema.additional_properties.as_ref(),
+                ))
+            }
+            _ => None,
+        };
+        if let Some(base) = base {
+            parts.push(base);
+        }
+        if !any_schema.all_of.is_empty() {
+            parts.extend(self.all_of_to_mcp_tool_property_type(&any_schema.all_of));
+        }
+        if !any_schema.one_of.is_empty() {
+            parts.extend(self.union_to_mcp_tool_property_type(&any_schema.one_of, schema_data));
+        }
+        if !any_schema.any_of.is_empty() {
+            parts.extend(self.union_to_mcp_tool_property_type(&any_schema.any_of, schema_data));
+        }
+
+        let parts = parts
+            .into_iter()
+            .map(|type_| MCPToolProperty {
+                name: String::new(),
+                description: None,
+                required: MCPToolPropertyRequired::Required,
+                type_,
+                constraints: Default::default(),
+                nullable: false,
+            })
+            .collect();
+        let mut type_ = merge_all_of(parts)?;
+        mark_required(&mut type_, &any_schema.required);
+        Some(type_)
+    }
+}
+
+/// Whether a property is required depends on the object containing it, see [`mark_required`].
+/// Properties with a single allowed value default to it, so the model doesn't have to send it.
+fn required(schema: &Schema, type_: &MCPToolPropertyType) -> MCPToolPropertyRequired {
+    match &schema.schema_data.default {
+        Some(default) if default.is_null() && schema.schema_data.nullable => {
+            MCPToolPropertyRequired::Default(default.clone())
+        }
+        Some(default) if default_matches(default, type_) => {
+            MCPToolPropertyRequired::Default(default.clone())
+        }
+        Some(default) => {
+            log::warn!("Ignoring default {} that doesn't match its schema", default);
+            MCPToolPropertyRequired::Optional
+        }
+        None => match type_ {
+            MCPToolPropertyType::Literal(value) => MCPToolPropertyRequired::Default(value.clone()),
+            _ => MCPToolPropertyRequired::Optional,
+        },
+    }
+}
+
+/// Whether `value` is valid for `type_`, a default that isn't can't be passed to Zod.
+fn default_matches(value: &serde_json::Value, type_: &MCPToolPropertyType) -> bool {
+    match type_ {
+        MCPToolPropertyType::String => value.is_string(),
+        MCPToolPropertyType::Number => value.is_number(),
+        MCPToolPropertyType::Integer => value.is_i64() || value.is_u64(),
+        MCPToolPropertyType::Boolean => value.is_boolean(),
+        MCPToolPropertyType::Array(items) => value.as_array().is_some_and(|values| {
+            values
+                .iter()
+                .all(|value| default_matches(value, &items.type_))
+        }),
+        MCPToolPropertyType::Map(values) => value.as_object().is_some_and(|value| {
+            value
+                .values()
+                .all(|value| default_matches(value, &values.type_))
+        }),
+        MCPToolPropertyType::Object(object) => value.as_object().is_some_and(|values| {
+            values.iter().all(|(name, value)| {
+                object
+                    .get(name)
+                    .is_some_and(|property| default_matches(value, &property.type_))
+            }) && object.iter().all(|(name, property)| {
+                !matches!(property.required, MCPToolPropertyRequired::Required)
+                    || values.contains_key(name)
+            })
+        }),
+        MCPToolPropertyType::Union { variants, .. } => variants
+            .iter()
+            .any(|variant| default_matches(value, &variant.type_)),
+        MCPToolPropertyType::Intersection(parts) => {
+            parts.iter().all(|part| default_matches(value, &part.type_))
+        }
+        MCPToolPropertyType::Literal(literal) => literal == value,
+        MCPToolPropertyType::Enum(values) => values.contains(value),
+        MCPToolPropertyType::Any | MCPToolPropertyType::Reference(_) => true,
+        MCPToolPropertyType::File => false,
+    }
+}
+
+fn required_properties(schema: &Schema) -> &[String] {
+    match &schema.schema_kind {
+        SchemaKind::Type(openapiv3::Type::Object(object_type)) => &object_type.required,
+        SchemaKind::Any(any_schema) => &any_schema.required,
+        _ => &[],
+    }
+}
+
+/// Marks the properties of an object that are listed in its `required` keyword. Properties with
+/// a single allowed value keep defaulting to it.
+fn mark_required(type_: &mut MCPToolPropertyType, required: &[String]) {
+    match type_ {
+        MCPToolPropertyType::Object(object) => {
+            for name in required {
+                if let Some(property) = object.get_mut(name)
+                    && !matches!(property.type_, MCPToolPropertyType::Literal(_))
+                {
+                    property.required = MCPToolPropertyRequired::Required;
+                }
+            }
+        }
+        MCPToolPropertyType::Intersection(parts) => {
+            for part in parts {
+                mark_required(&mut part.type_, required);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn constraints(schema: &Schema) -> MCPToolPropertyConstraints {
+    match &schema.schema_kind {
+        SchemaKind::Type(openapiv3::Type::String(string_type)) => MCPToolPropertyConstraints {
+            min_length: string_type.min_length,
+            max_length: string_type.max_length,
+            pattern: string_type.pattern.clone(),
+            format: format_name(&string_type.format),
+            ..Default::default()
+        },
+        SchemaKind::Type(openapiv3::Type::Number(number_type)) => MCPToolPropertyConstraints {
+            minimum: number_type.minimum,
+            exclusive_minimum: number_type.exclusive_minimum,
+            maximum: number_type.maximum,
+            exclusive_maximum: number_type.exclusive_maximum,
+            multiple_of: number_type.multiple_of,
+            format: format_name(&number_type.format),
+            ..Default::default()
+        },
+        SchemaKind::Type(openapiv3::Type::Integer(integer_type)) => MCPToolPropertyConstraints {
+            minimum: integer_type.minimum.map(|minimum| minimum as f64),
+            exclusive_minimum: integer_type.exclusive_minimum,
+            maximum: integer_type.maximum.map(|maximum| maximum as f64),
+            exclusive_maximum: integer_type.exclusive_maximum,
+            multiple_of: integer_type
+                .multiple_of
+                .map(|multiple_of| multiple_of as f64),
+            format: format_name(&integer_type.format),
+            ..Default::default()
+        },
+        SchemaKind::Type(openapiv3::Type::Array(array_type)) => MCPToolPropertyConstraints {
+            min_items: array_type.min_items,
+            max_items: array_type.max_items,
+            unique_items: array_type.unique_items,
+            ..Default::default()
+        },
+        SchemaKind::Type(openapiv3::Type::Object(object_type)) => MCPToolPropertyConstraints {
+            additional_properties: accepts_additional_properties(
+                object_type.properties.is_empty(),
+                object_type.additional_properties.as_ref(),
+            ),
+            ..Default::default()
+        },
+        SchemaKind::Any(any_schema) => MCPToolPropertyConstraints {
+            minimum: any_schema.minimum,
+            exclusive_minimum: any_schema.exclusive_minimum.unwrap_or_default(),
+            maximum: any_schema.maximum,
+            exclusive_maximum: any_schema.exclusive_maximum.unwrap_or_default(),
+            multiple_of: any_schema.multiple_of,
+            min_length: any_schema.min_length,
+            max_length: any_schema.max_length,
+            pattern: any_schema.pattern.clone(),
+            min_items: any_schema.min_items,
+            max_items: any_schema.max_items,
+            unique_items: any_schema.unique_items.unwrap_or_default(),
+            format: any_schema.format.clone(),
+            additional_properties: accepts_additional_properties(
+                any_schema.properties.is_empty(),
+                any_schema.additional_properties.as_ref(),
+            ),
+        },
+        _ => Default::default(),
+    }
+}
+
+/// Whether an object keeps properties it doesn't list, which OpenAPI allows unless
+/// `additionalProperties` is `false`. A schema for them is only enforced for maps.
+fn accepts_additional_properties(
+    no_properties: bool,
+    additional_properties: Option<&AdditionalProperties>,
+) -> bool {
+    match additional_properties {
+        Some(AdditionalProperties::Any(any)) => *any,
+        Some(AdditionalProperties::Schema(_)) | None => !no_properties,
+    }
+}
+
+/// The `format` as written in the schema, whether `openapiv3` knows it or not.
+fn format_name<T: serde::Serialize>(format: &VariantOrUnknownOrEmpty<T>) -> Option<String> {
+    match format {
+        VariantOrUnknownOrEmpty::Item(format) => serde_json::to_value(format)
+            .ok()
+            .and_then(|format| format.as_str().map(str::to_string)),
+        VariantOrUnknownOrEmpty::Unknown(format) => Some(format.clone()),
+        VariantOrUnknownOrEmpty::Empty => None,
+    }
+}
+
+/// An `enum` with a single value is a literal, an empty one doesn't restrict the values.
+fn enumeration_to_mcp_tool_property_type<T: serde::Serialize>(
+    enumeration: &[T],
+) -> Option<MCPToolPropertyType> {
+    let mut values: Vec<_> = enumeration
+        .iter()
+        .filter_map(|value| serde_json::to_value(value).ok())
+        .collect();
+    match values.len() {
+        0 => None,
+        1 => values.pop().map(MCPToolPropertyType::Literal),
+        _ => Some(MCPToolPropertyType::Enum(values)),
+    }
+}
+
+/// Returns the discriminator value that selects the schema at `reference`.
+fn discriminator_value(discriminator: &Discriminator, reference: &str) -> Option<String> {
+    let schema_name = reference.split('/').next_back()?;
+    discriminator
+        .mapping
+        .iter()
+        .find(|(_, target)| target.as_str() == reference || target.as_str() == schema_name)
+        .map(|(value, _)| value.clone())
+        .or_else(|| Some(schema_name.to_string()))
+}
+
+/// Merges the parts of an `allOf` into a single object if they are all objects, otherwise they
+/// become an intersection.
+fn merge_all_of(parts: Vec<MCPToolProperty>) -> Option<MCPToolPropertyType> {
+    let mut parts: Vec<_> = parts
+        .into_iter()
+        // a free-form object doesn't restrict the other parts
+        .filter(|part| match &part.type_ {
+            MCPToolPropertyType::Any => false,
+            MCPToolPropertyType::Map(values) => !matches!(values.type_, MCPToolPropertyType::Any),
+            _ => true,
+        })
+        .map(|mut part| {
+            part.required = MCPToolPropertyRequired::Required;
+            part
+        })
+        .collect();
+
+    if parts.is_empty() {
+        return Some(MCPToolPropertyType::Any);
+    }
+    if parts.len() == 1 {
+        return parts.pop().map(|part| part.type_);
+    }
+
+    if parts
+        .iter()
+        .all(|part| matches!(part.type_, MCPToolPropertyType::Object(_)))
+    {
+        let mut merged = BTreeMap::new();
+        for part in parts {
+            if let MCPToolPropertyType::Object(object) = part.type_ {
+                merged.extend(object);
+            }
+        }
+        return Some(MCPToolPropertyType::Object(merged));
+    }
+
+    Some(MCPToolPropertyType::Intersection(parts))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mcp_server::test_specs::openapi_with_schemas;
+
+    /// Converts the component schema `name` the way a request body referencing it would be.
+    fn convert(
+        openapi: &OpenAPI,
+        name: &str,
+        max_depth: u32,
+    ) -> (MCPToolProperty, BTreeMap<String, MCPToolProperty>) {
+        let mut converter = SchemaConverter::new(openapi, max_depth);
+        let property = converter
+            .schema_ref_to_mcp_tool_property(&ReferenceOr::Reference {
+                reference: format!("#/components/schemas/{}", name),
+            })
+            .unwrap();
+        (property, converter.into_definitions())
+    }
+
+    #[test]
+    fn test_one_of_with_discriminator_mapping() {
+        let openapi = openapi_with_schemas(serde_json::json!({
+            "Cat": {
+                "type": "object",
+                "properties": {
+                    "kind": { "type": "string" },
+                    "meows": { "type": "boolean" }
+                }
+            },
+            "Dog": {
+                "type": "object",
+                "properties": {
+                    "kind": { "type": "string" },
+                    "barks": { "type": "boolean" }
+                }
+            },
+            "Pet": {
+                "oneOf": [
+                    { "$ref": "#/components/schemas/Cat" },
+                    { "$ref": "#/components/schemas/Dog" }
+                ],
+                "discriminator": {
+                    "propertyName": "kind",
+                    "mapping": { "cat": "#/components/schemas/Cat" }
+                }
+            }
+        }));
+        let (property, _) = convert(&openapi, "Pet", DEFAULT_MAX_SCHEMA_DEPTH);
+
+        let MCPToolPropertyType::Union {
+            variants,
+            discriminator,
+        } = property.type_
+        else {
+            panic!("expected union, got {:?}", property.type_);
+        };
+        assert_eq!(discriminator.as_deref(), Some("kind"));
+        let kinds: Vec<_> = variants
+            .iter()
+            .map(|variant| match &variant.type_ {
+                MCPToolPropertyType::Object(object) => match &object["kind"].type_ {
+                    MCPToolPropertyType::Literal(value) => value.clone(),
+                    other => panic!("expected literal, got {:?}", other),
+                },
+                other => panic!("expected object, got {:?}", other),
+            })
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![serde_json::json!("cat"), serde_json::json!("Dog")]
+        );
+    }
+
+    #[test]
+    fn test_all_of_objects_are_merged() {
+        let openapi = openapi_with_schemas(serde_json::json!({
+            "Base": {
+                "type": "object",
+                "properties": { "id": { "type": "string" } }
+            },
+            "Extended": {
+                "allOf": [
+                    { "$ref": "#/components/schemas/Base" },
+                    { "type": "object", "properties": { "name": { "type": "string" } } }
+                ]
+            }
+        }));
+        let (property, _) = convert(&openapi, "Extended", DEFAULT_MAX_SCHEMA_DEPTH);
+
+        let MCPToolPropertyType::Object(object) = property.type_ else {
+            panic!("expected object, got {:?}", property.type_);
+        };
+        assert_eq!(object.keys().collect::<Vec<_>>(), vec!["id", "name"]);
+    }
+
+    #[test]
+    fn test_self_referencing_schema_becomes_definition() {
+        let openapi = openapi_with_schemas(serde_json::json!({
+            "TreeNode": {
+                "type": "object",
+                "properties": {
+                    "value": { "type": "string" },
+                    "children": {
+                        "type": "array",
+                        "items": { "$ref": "#/components/schemas/TreeNode" }
+                    }
+                }
+            }
+        }));
+
+        let (property, definitions) = convert(&openapi, "TreeNode", DEFAULT_MAX_SCHEMA_DEPTH);
+
+        assert!(matches!(
+            &property.type_,
+            MCPToolPropertyType::Reference(name) if name == "tree_node_schema"
+        ));
+        let MCPToolPropertyType::Object(object) = &definitions["tree_node_schema"].type_ else {
+            panic!(
+                "expected object, got {:?}",
+                definitions["tree_node_schema"].type_
+            );
+        };
+        let MCPToolPropertyType::Array(items) = &object["children"].type_ else {
+            panic!("expected array, got {:?}", object["children"].type_);
+        };
+        assert!(matches!(
+            &items.type_,
+            MCPToolPropertyType::Reference(name) if name == "tree_node_schema"
+        ));
+    }
+
+    #[test]
+    fn test_mutually_recursive_schemas() {
+        let openapi = openapi_with_schemas(serde_json::json!({
+            "Person": {
+                "type": "object",
+                "properties": {
+                    "employer": { "$ref": "#/components/schemas/Company" }
+                }
+            },
+            "Company": {
+                "type": "object",
+                "properties": {
+                    "ceo": { "$ref": "#/components/schemas/Person" }
+                }
+            }
+        }));
+
+        let (property, definitions) = convert(&openapi, "Person", DEFAULT_MAX_SCHEMA_DEPTH);
+
+        assert!(matches!(property.type_, MCPToolPropertyType::Reference(_)));
+        assert_eq!(
+            definitions.keys().collect::<Vec<_>>(),
+            vec!["person_schema"]
+        );
+    }
+
+    #[test]
+    fn test_schemas_deeper_than_max_depth_accept_anything() {
+        let openapi = openapi_with_schemas(serde_json::json!({
+            "Outer": {
+                "type": "object",
+                "properties": {
+                    "inner": {
+                        "type": "object",
+                        "properties": { "value": { "type": "string" } }
+                    }
+                }
+            }
+        }));
+
+        let (property, definitions) = convert(&openapi, "Outer", 1);
+
+        assert!(definitions.is_empty());
+        let MCPToolPropertyType::Object(object) = property.type_ else {
+            panic!("expected object, got {:?}", property.type_);
+        };
+        assert!(matches!(object["inner"].type_, MCPToolPropertyType::Any));
+    }
+
+    #[test]
+    fn test_enum_properties() {
+        let openapi = openapi_with_schemas(serde_json::json!({
+            "Order": {
+                "type": "object",
+                "properties": {
+                    "status": { "type": "string", "enum": ["placed", "shipped"] },
+                    "priority": { "type": "integer", "enum": [1, 2, 3] },
+                    "kind": { "type": "string", "enum": ["order"] }
+                }
+            }
+        }));
+
+        let (property, _) = convert(&openapi, "Order", DEFAULT_MAX_SCHEMA_DEPTH);
+
+        let MCPToolPropertyType::Object(object) = property.type_ else {
+            panic!("expected object, got {:?}", property.type_);
+        };
+        assert!(matches!(
+            &object["status"].type_,
+            MCPToolPropertyType::Enum(values) if values == &vec![serde_json::json!("placed"), serde_json::json!("shipped")]
+        ));
+        assert!(matches!(
+            &object["priority"].type_,
+            MCPToolPropertyType::Enum(values) if values.len() == 3
+        ));
+        assert!(matches!(
+            &object["kind"].type_,
+            MCPToolPropertyType::Literal(value) if value == "order"
+        ));
+        assert!(matches!(
+            &object["kind"].required,
+            MCPToolPropertyRequired::Default(value) if value == "order"
+        ));
+    }
+
+    #[test]
+    fn test_validation_constraints() {
+        let openapi = openapi_with_schemas(serde_json::json!({
+            "User": {
+                "type": "object",
+                "properties": {
+                    "email": { "type": "string", "format": "email", "maxLength": 254 },
+                    "age": { "type": "integer", "minimum": 0, "exclusiveMaximum": true, "maximum": 150 },
+                    "tags": { "type": "array", "items": { "type": "string" }, "minItems": 1, "uniqueItems": true }
+                }
+            }
+        }));
+
+        let (property, _) = convert(&openapi, "User", DEFAULT_MAX_SCHEMA_DEPTH);
+
+        let MCPToolPropertyType::Object(object) = property.type_ else {
+            panic!("expected object, got {:?}", property.type_);
+        };
+        let email = &object["email"].constraints;
+        assert_eq!(email.format.as_deref(), Some("email"));
+        assert_eq!(email.max_length, Some(254));
+        let age = &object["age"].constraints;
+        assert_eq!(age.minimum, Some(0.0));
+        assert_eq!(age.maximum, Some(150.0));
+        assert!(!age.exclusive_minimum);
+        assert!(age.exclusive_maximum);
+        let tags = &object["tags"].constraints;
+        assert_eq!(tags.min_items, Some(1));
+        assert!(tags.unique_items);
+    }
+
+    #[test]
+    fn test_all_of_required_applies_across_parts() {
+        let openapi = openapi_with_schemas(serde_json::json!({
+            "Base": {
+                "type": "object",
+                "properties": { "id": { "type": "string" } }
+            },
+            "Named": {
+                "allOf": [
+                    { "$ref": "#/components/schemas/Base" },
+                    {
+                        "type": "object",
+                        "required": ["id", "name"],
+                        "properties": { "name": { "type": "string" } }
+                    }
+                ]
+            }
+        }));
+
+        let (property, _) = convert(&openapi, "Named", DEFAULT_MAX_SCHEMA_DEPTH);
+
+        let MCPToolPropertyType::Object(object) = property.type_ else {
+            panic!("expected object, got {:?}", property.type_);
+        };
+        assert!(matches!(
+            object["id"].required,
+            MCPToolPropertyRequired::Required
+        ));
+        assert!(matches!(
+            object["name"].required,
+            MCPToolPropertyRequired::Required
+        ));
+    }
+
+    #[test]
+    fn test_defaults_nullable_and_read_only() {
+        let openapi = openapi_with_schemas(serde_json::json!({
+            "Item": {
+                "type": "object",
+                "properties": {
+                    "id": { "type": "string", "readOnly": true },
+                    "password": { "type": "string", "writeOnly": true },
+                    "limit": { "type": "integer", "default": 10 },
+                    "offset": { "type": "integer", "default": "0" },
+                    "note": { "type": "string", "nullable": true }
+                }
+            }
+        }));
+
+        let (property, _) = convert(&openapi, "Item", DEFAULT_MAX_SCHEMA_DEPTH);
+
+        let MCPToolPropertyType::Object(object) = property.type_ else {
+            panic!("expected object, got {:?}", property.type_);
+        };
+        assert_eq!(
+            object.keys().collect::<Vec<_>>(),
+            vec!["limit", "note", "offset", "password"]
+        );
+        assert!(matches!(
+            &object["limit"].required,
+            MCPToolPropertyRequired::Default(value) if value == 10
+        ));
+        assert!(matches!(
+            object["offset"].required,
+            MCPToolPropertyRequired::Optional
+        ));
+        assert!(object["note"].nullable);
+    }
+
+    #[test]
+    fn test_additional_properties() {
+        let openapi = openapi_with_schemas(serde_json::json!({
+            "Resource": {
+                "type": "object",
+                "properties": {
+                    "labels": {
+                        "type": "object",
+                        "additionalProperties": { "type": "string" }
+                    },
+                    "metadata": {
+                        "type": "object",
+                        "properties": { "owner": { "type": "string" } },
+                        "additionalProperties": true
+                    }
+                }
+            }
+        }));
+
+        let (property, _) = convert(&openapi, "Resource", DEFAULT_MAX_SCHEMA_DEPTH);
+
+        let MCPToolPropertyType::Object(object) = property.type_ else {
+            panic!("expected object, got {:?}", property.type_);
+        };
+        let MCPToolPropertyType::Map(values) = &object["labels"].type_ else {
+            panic!("expected map, got {:?}", object["labels"].type_);
+        };
+        assert!(matches!(values.type_, MCPToolPropertyType::String));
+        assert!(matches!(
+            object["metadata"].type_,
+            MCPToolPropertyType::Object(_)
+        ));
+        assert!(object["metadata"].constraints.additional_properties);
+    }
+
+    #[test]
+    fn test_objects_accept_additional_properties_by_default() {
+        let openapi = openapi_with_schemas(serde_json::json!({
+            "Resource": {
+                "type": "object",
+                "properties": {
+                    "metadata": { "type": "object" },
+                    "owner": {
+                        "type": "object",
+                        "properties": { "name": { "type": "string" } }
+                    },
+                    "empty": { "type": "object", "additionalProperties": false }
+                }
+            }
+        }));
+
+        let (property, _) = convert(&openapi, "Resource", DEFAULT_MAX_SCHEMA_DEPTH);
+
+        assert!(property.constraints.additional_properties);
+        let MCPToolPropertyType::Object(object) = property.type_ else {
+            panic!("expected object, got {:?}", property.type_);
+        };
+        let MCPToolPropertyType::Map(values) = &object["metadata"].type_ else {
+            panic!("expected map, got {:?}", object["metadata"].type_);
+        };
+        assert!(matches!(values.type_, MCPToolPropertyType::Any));
+        assert!(object["owner"].constraints.additional_properties);
+        let MCPToolPropertyType::Object(empty) = &object["empty"].type_ else {
+            panic!("expected object, got {:?}", object["empty"].type_);
+        };
+        assert!(empty.is_empty());
+        assert!(!object["empty"].constraints.additional_properties);
+    }
+}
@@ -0,0 +1,189 @@
+//! Bundling of references to other files into a single document.
+//!
+//! Every document referenced with a relative path (e.g. `./common.yaml#/components/schemas/Error`)
+//! is loaded and stored in the root document under [`EXTERNAL_DOCUMENTS_KEY`], keyed by its path
+//! relative to the root document. References are rewritten to point there, so afterwards every
+//! reference is a same-document JSON pointer. References inside the loaded documents are
+//! rewritten the same way, relative to the document they appear in.
+
+use std::path::{Component, Path, PathBuf};
+
+use super::pointer::{parse_fragment, split_reference, to_reference};
+
+/// Root document extension that holds the bundled external documents.
+pub const EXTERNAL_DOCUMENTS_KEY: &str = "x-openapi2mcp-external-documents";
+
+/// Bundles the documents referenced from `document` that are located relative to `base_dir`.
+pub fn bundle_external_refs(
+    document: &mut serde_json::Value,
+    base_dir: &Path,
+) -> anyhow::Result<()> {
+    let mut bundler = Bundler {
+        base_dir,
+        documents: serde_json::Map::new(),
+    };
+    bundler.rewrite_refs(document, None)?;
+
+    if !bundler.documents.is_empty() {
+        let serde_json::Value::Object(root) = document else {
+            anyhow::bail!("OpenAPI document must be an object");
+        };
+        root.insert(
+            EXTERNAL_DOCUMENTS_KEY.to_string(),
+            serde_json::Value::Object(bundler.documents),
+        );
+    }
+    Ok(())
+}
+
+struct Bundler<'a> {
+    base_dir: &'a Path,
+    documents: serde_json::Map<String, serde_json::Value>,
+}
+
+impl Bundler<'_> {
+    /// Rewrites the references in `value`, which is part of the external document at
+    /// `document_path`, or of the root document if `None`.
+    fn rewrite_refs(
+        &mut self,
+        value: &mut serde_json::Value,
+        document_path: Option<&str>,
+    ) -> anyhow::Result<()> {
+        match value {
+            serde_json::Value::Object(object) => {
+                for (key, child) in object.iter_mut() {
+                    match child {
+                        serde_json::Value::String(reference) if key == "$ref" => {
+                            *reference = self.rebase(reference, document_path)?;
+                        }
+                        _ => self.rewrite_refs(child, document_path)?,
+                    }
+                }
+            }
+            serde_json::Value::Array(items) => {
+                for item in items {
+                    self.rewrite_refs(item, document_path)?;
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn rebase(&mut self, reference: &str, document_path: Option<&str>) -> anyhow::Result<String> {
+        let (target, fragment) = split_reference(reference);
+        let target_path = match (target, document_path) {
+            ("", None) => return Ok(reference.to_string()),
+            ("", Some(document_path)) => document_path.to_string(),
+            (target, _) if target.contains("://") => {
+                anyhow::bail!("Remote references are not supported: {}", reference)
+            }
+            (target, document_path) => {
+                let document_dir = document_path
+                    .and_then(|path| Path::new(path).parent())
+                    .unwrap_or(Path::new(""));
+                normalize_path(&document_dir.join(target))
+            }
+        };
+        self.load(&target_path)?;
+
+        let mut segments = vec![EXTERNAL_DOCUMENTS_KEY.to_string(), target_path];
+        segments.extend(parse_fragment(fragment)?);
+        Ok(to_reference(&segments))
+    }
+
+    fn load(&mut self, path: &str) -> anyhow::Result<()> {
+        if self.documents.contains_key(path) {
+            return Ok(());
+        }
+        log::info!("Loading referenced document {}", path);
+
+        // Placeholder so references back to this document don't load it again
+        self.documents
+            .insert(path.to_string(), serde_json::Value::Null);
+        let mut document = super::read_document(&self.base_dir.join(path))?;
+        self.rewrite_refs(&mut document, Some(path))?;
+        self.documents.insert(path.to_string(), document);
+        Ok(())
+    }
+}
+
+/// Lexically normalizes a relative path and joins its components with `/`, so the same document
+/// always gets the same key.
+fn normalize_path(path: &Path) -> String {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                if !normalized.pop() {
+                    normalized.push("..");
+                }
+            }
+            component => normalized.push(component),
+        }
+    }
+    normalized
+        .components()
+        .map(|component| component.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalize_relative_paths() {
+        assert_eq!(normalize_path(Path::new("./common.yaml")), "common.yaml");
+        assert_eq!(
+            normalize_path(Path::new("schemas/../common/./error.yaml")),
+            "common/error.yaml"
+        );
+    }
+
+    #[test]
+    fn bundle_nested_external_refs() {
+        let dir = std::env::temp_dir().join("openapi2mcp-bundle-nested-external-refs");
+        std::fs::create_dir_all(dir.join("schemas")).unwrap();
+        std::fs::write(
+            dir.join("schemas/pet.yaml"),
+            "Pet:\n  type: object\n  properties:\n    owner:\n      $ref: '../common.json#/components/schemas/User'\n    tag:\n      $ref: '#/Tag'\nTag:\n  type: string\n",
+        )
+        .unwrap();
+        std::fs::write(
+            dir.join("common.json"),
+            r#"{ "components": { "schemas": { "User": { "type": "string" } } } }"#,
+        )
+        .unwrap();
+
+        let mut document = serde_json::json!({
+            "components": {
+                "schemas": {
+                    "Pet": { "$ref": "./schemas/pet.yaml#/Pet" },
+                    "Local": { "$ref": "#/components/schemas/Pet" }
+                }
+            }
+        });
+        bundle_external_refs(&mut document, &dir).unwrap();
+
+        let schemas = &document["components"]["schemas"];
+        assert_eq!(
+            schemas["Pet"]["$ref"],
+            "#/x-openapi2mcp-external-documents/schemas~1pet.yaml/Pet"
+        );
+        assert_eq!(schemas["Local"]["$ref"], "#/components/schemas/Pet");
+
+        let pet = &document[EXTERNAL_DOCUMENTS_KEY]["schemas/pet.yaml"]["Pet"];
+        assert_eq!(
+            pet["properties"]["owner"]["$ref"],
+            "#/x-openapi2mcp-external-documents/common.json/components/schemas/User"
+        );
+        assert_eq!(
+            pet["properties"]["tag"]["$ref"],
+            "#/x-openapi2mcp-external-documents/schemas~1pet.yaml/Tag"
+        );
+        assert!(document[EXTERNAL_DOCUMENTS_KEY]["common.json"].is_object());
+    }
+}
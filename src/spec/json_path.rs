@@ -0,0 +1,531 @@
+//! The subset of JSONPath ([RFC 9535](https://www.rfc-editor.org/rfc/rfc9535)) used by overlays to
+//! select the parts of a document they change.
+//!
+//! Supported are the root `$`, names (`.name`, `['name']`), indices (`[0]`, `[-1]`), wildcards
+//! (`.*`, `[*]`), descendants (`..name`, `..*`) and filters (`[?@.in == 'query']`, optionally in
+//! parentheses). Filters compare a relative path of names and indices to a literal with `==` or
+//! `!=`, or test its existence, and combine those with `&&`, `||` and `!`.
+
+use serde_json::Value;
+
+/// Where a selected value is in the document.
+pub type Location = Vec<Step>;
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Step {
+    Key(String),
+    Index(usize),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct JsonPath {
+    segments: Vec<Segment>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Segment {
+    descendants: bool,
+    selector: Selector,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Selector {
+    Name(String),
+    Index(i64),
+    Wildcard,
+    Filter(Filter),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Filter {
+    Exists(Vec<Step>),
+    Equals(Vec<Step>, Value),
+    NotEquals(Vec<Step>, Value),
+    And(Box<Filter>, Box<Filter>),
+    Or(Box<Filter>, Box<Filter>),
+    Not(Box<Filter>),
+}
+
+impl JsonPath {
+    pub fn parse(path: &str) -> anyhow::Result<Self> {
+        let mut parser = Parser {
+            path,
+            chars: path.chars().collect(),
+            position: 0,
+        };
+        parser.expect('$')?;
+        let mut segments = Vec::new();
+        while parser.peek().is_some() {
+            segments.push(parser.segment()?);
+        }
+        Ok(Self { segments })
+    }
+
+    /// Locations of the values the path selects in `document`, in document order.
+    pub fn select(&self, document: &Value) -> Vec<Location> {
+        let mut selected = vec![(Vec::new(), document)];
+        for segment in &self.segments {
+            let mut next = Vec::new();
+            for (location, value) in selected {
+                if segment.descendants {
+                    for (location, value) in descendants(location, value) {
+                        segment.selector.apply(location, value, &mut next);
+                    }
+                } else {
+                    segment.selector.apply(location, value, &mut next);
+                }
+            }
+            selected = next;
+        }
+        selected.into_iter().map(|(location, _)| location).collect()
+    }
+}
+
+impl Selector {
+    fn apply<'a>(
+        &self,
+        location: Location,
+        value: &'a Value,
+        selected: &mut Vec<(Location, &'a Value)>,
+    ) {
+        match (self, value) {
+            (Selector::Name(name), Value::Object(object)) => {
+                if let Some(child) = object.get(name) {
+                    selected.push((child_location(&location, Step::Key(name.clone())), child));
+                }
+            }
+            (Selector::Index(index), Value::Array(array)) => {
+                let index = match *index < 0 {
+                    true => array.len() as i64 + index,
+                    false => *index,
+                };
+                if let Ok(index) = usize::try_from(index)
+                    && let Some(child) = array.get(index)
+                {
+                    selected.push((child_location(&location, Step::Index(index)), child));
+                }
+            }
+            (Selector::Wildcard | Selector::Filter(_), _) => {
+                for (step, child) in children(value) {
+                    let matches = match self {
+                        Selector::Filter(filter) => filter.matches(child),
+                        _ => true,
+                    };
+                    if matches {
+                        selected.push((child_location(&location, step), child));
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+impl Filter {
+    fn matches(&self, value: &Value) -> bool {
+        match self {
+            Filter::Exists(path) => relative(value, path).is_some(),
+            Filter::Equals(path, literal) => relative(value, path) == Some(literal),
+            Filter::NotEquals(path, literal) => relative(value, path) != Some(literal),
+            Filter::And(left, right) => left.matches(value) && right.matches(value),
+            Filter::Or(left, right) => left.matches(value) || right.matches(value),
+            Filter::Not(filter) => !filter.matches(value),
+        }
+    }
+}
+
+fn relative<'a>(value: &'a Value, path: &[Step]) -> Option<&'a Value> {
+    path.iter().try_fold(value, |value, step| match step {
+        Step::Key(key) => value.get(key),
+        Step::Index(index) => value.get(index),
+    })
+}
+
+fn child_location(location: &Location, step: Step) -> Location {
+    let mut location = location.clone();
+    location.push(step);
+    location
+}
+
+fn children(value: &Value) -> Vec<(Step, &Value)> {
+    match value {
+        Value::Object(object) => object
+            .iter()
+            .map(|(key, child)| (Step::Key(key.clone()), child))
+            .collect(),
+        Value::Array(array) => array
+            .iter()
+            .enumerate()
+            .map(|(index, child)| (Step::Index(index), child))
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// The value itself and all the values nested in it.
+fn descendants(location: Location, value: &Value) -> Vec<(Location, &Value)> {
+    let mut descendants = vec![(location.clone(), value)];
+    for (step, child) in children(value) {
+        descendants.extend(self::descendants(child_location(&location, step), child));
+    }
+    descendants
+}
+
+struct Parser<'a> {
+    path: &'a str,
+    chars: Vec<char>,
+    position: usize,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.position).copied()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.position += 1;
+        }
+    }
+
+    fn eat(&mut self, s: &str) -> bool {
+        let matches = s
+            .chars()
+            .enumerate()
+            .all(|(offset, c)| self.chars.get(self.position + offset) == Some(&c));
+        if matches {
+            self.position += s.chars().count();
+        }
+        matches
+    }
+
+    fn expect(&mut self, c: char) -> anyhow::Result<()> {
+        self.skip_whitespace();
+        match self.peek() == Some(c) {
+            true => {
+                self.position += 1;
+                Ok(())
+            }
+            false => Err(self.error(&format!("expected {c}"))),
+        }
+    }
+
+    fn error(&self, message: &str) -> anyhow::Error {
+        anyhow::anyhow!(
+            "Invalid JSONPath {}: {} at position {}",
+            self.path,
+            message,
+            self.position
+        )
+    }
+
+    fn segment(&mut self) -> anyhow::Result<Segment> {
+        let descendants = self.eat("..");
+        let selector = if descendants && self.peek() != Some('[') || self.eat(".") {
+            match self.eat("*") {
+                true => Selector::Wildcard,
+                false => Selector::Name(self.name()?),
+            }
+        } else if self.eat("[") {
+            let selector = self.bracket_selector()?;
+            self.expect(']')?;
+            selector
+        } else {
+            return Err(self.error("expected . or ["));
+        };
+        Ok(Segment {
+            descendants,
+            selector,
+        })
+    }
+
+    fn name(&mut self) -> anyhow::Result<String> {
+        let start = self.position;
+        while self
+            .peek()
+            .is_some_and(|c| c.is_alphanumeric() || matches!(c, '_' | '-' | '$'))
+        {
+            self.position += 1;
+        }
+        match self.position > start {
+            true => Ok(self.chars[start..self.position].iter().collect()),
+            false => Err(self.error("expected a name")),
+        }
+    }
+
+    fn bracket_selector(&mut self) -> anyhow::Result<Selector> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some('*') => {
+                self.position += 1;
+                Ok(Selector::Wildcard)
+            }
+            Some('\'' | '"') => Ok(Selector::Name(self.string()?)),
+            Some('?') => {
+                self.position += 1;
+                Ok(Selector::Filter(self.or()?))
+            }
+            _ => {
+                let start = self.position;
+                if self.peek() == Some('-') {
+                    self.position += 1;
+                }
+                while self.peek().is_some_and(|c| c.is_ascii_digit()) {
+                    self.position += 1;
+                }
+                let index: String = self.chars[start..self.position].iter().collect();
+                index
+                    .parse()
+                    .map(Selector::Index)
+                    .map_err(|_| self.error("expected a name, index, * or filter"))
+            }
+        }
+    }
+
+    fn string(&mut self) -> anyhow::Result<String> {
+        let Some(quote) = self.peek() else {
+            return Err(self.error("expected a string"));
+        };
+        self.position += 1;
+        let mut string = String::new();
+        loop {
+            match self.peek() {
+                Some('\\') => {
+                    self.position += 1;
+                    let escaped = match self.peek() {
+                        Some('b') => '\u{8}',
+                        Some('f') => '\u{c}',
+                        Some('n') => '\n',
+                        Some('r') => '\r',
+                        Some('t') => '\t',
+                        Some(c @ ('/' | '\\' | '\'' | '"')) => c,
+                        Some('u') => self.unicode_escape()?,
+                        _ => return Err(self.error("invalid escape")),
+                    };
+                    string.push(escaped);
+                }
+                Some(c) if c == quote => break,
+                Some(c) => string.push(c),
+                None => return Err(self.error("unterminated string")),
+            }
+            self.position += 1;
+        }
+        self.position += 1;
+        Ok(string)
+    }
+
+    /// Decodes the `\uXXXX` escape starting at the `u`, joined with the low surrogate following a
+    /// high one, and stops at its last digit.
+    fn unicode_escape(&mut self) -> anyhow::Result<char> {
+        let high = self.hex_digits()?;
+        let code = match high {
+            0xD800..0xDC00 => {
+                if self.chars.get(self.position + 1..self.position + 3) != Some(&['\\', 'u']) {
+                    return Err(self.error("expected a low surrogate"));
+                }
+                self.position += 2;
+                match self.hex_digits()? {
+                    low @ 0xDC00..0xE000 => 0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00),
+                    _ => return Err(self.error("expected a low surrogate")),
+                }
+            }
+            code => code,
+        };
+        char::from_u32(code).ok_or_else(|| self.error("invalid unicode escape"))
+    }
+
+    /// Reads the four hex digits after the current character and stops at the last of them.
+    fn hex_digits(&mut self) -> anyhow::Result<u32> {
+        let digits = self
+            .chars
+            .get(self.position + 1..self.position + 5)
+            .filter(|digits| digits.iter().all(char::is_ascii_hexdigit))
+            .ok_or_else(|| self.error("expected four hex digits"))?;
+        let code = u32::from_str_radix(&digits.iter().collect::<String>(), 16)?;
+        self.position += 4;
+        Ok(code)
+    }
+
+    fn or(&mut self) -> anyhow::Result<Filter> {
+        let mut filter = self.and()?;
+        loop {
+            self.skip_whitespace();
+            if !self.eat("||") {
+                return Ok(filter);
+            }
+            filter = Filter::Or(Box::new(filter), Box::new(self.and()?));
+        }
+    }
+
+    fn and(&mut self) -> anyhow::Result<Filter> {
+        let mut filter = self.not()?;
+        loop {
+            self.skip_whitespace();
+            if !self.eat("&&") {
+                return Ok(filter);
+            }
+            filter = Filter::And(Box::new(filter), Box::new(self.not()?));
+        }
+    }
+
+    fn not(&mut self) -> anyhow::Result<Filter> {
+        self.skip_whitespace();
+        if self.eat("!") {
+            return Ok(Filter::Not(Box::new(self.not()?)));
+        }
+        if self.eat("(") {
+            let filter = self.or()?;
+            self.expect(')')?;
+            return Ok(filter);
+        }
+        self.comparison()
+    }
+
+    fn comparison(&mut self) -> anyhow::Result<Filter> {
+        self.expect('@')?;
+        let mut path = Vec::new();
+        loop {
+            if self.eat(".") {
+                path.push(Step::Key(self.name()?));
+            } else if self.eat("[") {
+                self.skip_whitespace();
+                match self.bracket_selector()? {
+                    Selector::Name(name) => path.push(Step::Key(name)),
+                    Selector::Index(index) if index >= 0 => path.push(Step::Index(index as usize)),
+                    _ => return Err(self.error("expected a name or index")),
+                }
+                self.expect(']')?;
+            } else {
+                break;
+            }
+        }
+
+        self.skip_whitespace();
+        if self.eat("==") {
+            Ok(Filter::Equals(path, self.literal()?))
+        } else if self.eat("!=") {
+            Ok(Filter::NotEquals(path, self.literal()?))
+        } else {
+            Ok(Filter::Exists(path))
+        }
+    }
+
+    fn literal(&mut self) -> anyhow::Result<Value> {
+        self.skip_whitespace();
+        if matches!(self.peek(), Some('\'' | '"')) {
+            return Ok(Value::String(self.string()?));
+        }
+        for (keyword, value) in [
+            ("true", Value::Bool(true)),
+            ("false", Value::Bool(false)),
+            ("null", Value::Null),
+        ] {
+            // `trueish` is not `true` followed by `ish`
+            let next = self.chars.get(self.position + keyword.len());
+            if !next.is_some_and(|c| c.is_alphanumeric() || *c == '_') && self.eat(keyword) {
+                return Ok(value);
+            }
+        }
+
+        let start = self.position;
+        while self
+            .peek()
+            .is_some_and(|c| c.is_ascii_digit() || matches!(c, '-' | '+' | '.' | 'e' | 'E'))
+        {
+            self.position += 1;
+        }
+        let number: String = self.chars[start..self.position].iter().collect();
+        serde_json::from_str::<serde_json::Number>(&number)
+            .map(Value::Number)
+            .map_err(|_| self.error("expected a string, number, true, false or null"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn select(path: &str, document: &Value) -> Vec<Location> {
+        JsonPath::parse(path).unwrap().select(document)
+    }
+
+    fn key(key: &str) -> Step {
+        Step::Key(key.to_string())
+    }
+
+    #[test]
+    fn test_select() {
+        let document = serde_json::json!({
+            "paths": {
+                "/pets": {
+                    "get": {
+                        "operationId": "listPets",
+                        "parameters": [
+                            { "name": "limit", "in": "query" },
+                            { "name": "X-Trace", "in": "header" }
+                        ]
+                    },
+                    "post": { "operationId": "createPet" }
+                }
+            }
+        });
+
+        assert_eq!(
+            select("$.paths['/pets'].get.parameters[-1]", &document),
+            [vec![
+                key("paths"),
+                key("/pets"),
+                key("get"),
+                key("parameters"),
+                Step::Index(1)
+            ]]
+        );
+        assert_eq!(
+            select("$.paths.*.*[?(@.operationId == 'createPet')]", &document),
+            Vec::<Location>::new()
+        );
+        assert_eq!(
+            select("$.paths.*[?@.operationId == 'createPet']", &document),
+            [vec![key("paths"), key("/pets"), key("post")]]
+        );
+        assert_eq!(
+            select("$..parameters[?(@.in != 'query' && @.name)]", &document).len(),
+            1
+        );
+        assert_eq!(select("$..operationId", &document).len(), 2);
+    }
+
+    #[test]
+    fn test_invalid_paths() {
+        assert!(JsonPath::parse("paths").is_err());
+        assert!(JsonPath::parse("$.paths[").is_err());
+        assert!(JsonPath::parse("$.paths['/pets'").is_err());
+        assert!(JsonPath::parse("$[?(@.a == )]").is_err());
+        assert!(JsonPath::parse("$[?(@.a == trueish)]").is_err());
+        assert!(JsonPath::parse("$[?(@.a == nulls)]").is_err());
+        assert!(JsonPath::parse("$['\\x']").is_err());
+        assert!(JsonPath::parse("$['\\u00G1']").is_err());
+        assert!(JsonPath::parse("$['\\uD83D']").is_err());
+    }
+
+    #[test]
+    fn test_string_escapes() {
+        let document = serde_json::json!({
+            "a\nb": 1,
+            "A'\"/\\": 2,
+            "\u{1F600}": 3,
+            "flag": { "on": true }
+        });
+
+        assert_eq!(select("$['a\\nb']", &document), [vec![key("a\nb")]]);
+        assert_eq!(
+            select("$['\\u0041\\'\\\"\\/\\\\']", &document),
+            [vec![key("A'\"/\\")]]
+        );
+        assert_eq!(
+            select("$[\"\\uD83D\\uDE00\"]", &document),
+            [vec![key("\u{1F600}")]]
+        );
+        assert_eq!(select("$[?(@.on == true)]", &document), [vec![key("flag")]]);
+    }
+}
@@ -0,0 +1,39 @@
+//! Loading of OpenAPI documents before they are deserialized into [`openapiv3::OpenAPI`].
+//!
+//! Documents are read as plain JSON values so they can be pre-processed, e.g. to bundle
+//! references to other files into the root document, or to rewrite schema keywords
+//! that [`openapiv3`] would drop.
+
+use std::{fs, path::Path};
+
+mod bundle;
+mod json_path;
+mod normalize;
+mod openapi31;
+mod overlay;
+mod path_items;
+pub mod pointer;
+mod swagger2;
+
+pub use bundle::bundle_external_refs;
+pub use normalize::const_to_enum;
+pub use openapi31::downgrade_openapi_31;
+pub use overlay::apply_overlay;
+pub use path_items::inline_path_item_refs;
+pub use swagger2::upgrade_swagger_2;
+
+/// Reads a JSON or YAML document, picking the format from the file extension.
+pub fn read_document(path: &Path) -> anyhow::Result<serde_json::Value> {
+    let content = fs::read_to_string(path)
+        .map_err(|e| anyhow::anyhow!("Failed to read {}: {}", path.display(), e))?;
+
+    let extension = path.extension().and_then(|s| s.to_str());
+    let document = match extension {
+        Some("json") => serde_json::from_str(&content)
+            .map_err(|e| anyhow::anyhow!("Failed to parse JSON: {}", e))?,
+        Some("yaml") | Some("yml") => serde_yaml::from_str(&content)
+            .map_err(|e| anyhow::anyhow!("Failed to parse YAML: {}", e))?,
+        _ => Err(anyhow::anyhow!("Unsupported file extension"))?,
+    };
+    Ok(document)
+}
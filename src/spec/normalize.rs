@@ -0,0 +1,101 @@
+//! Rewrites of schema keywords that [`openapiv3`] doesn't know into equivalent ones it does.
+
+use serde_json::Value;
+
+use super::bundle::EXTERNAL_DOCUMENTS_KEY;
+
+/// Keys whose values map names to schemas, rather than being schemas themselves.
+pub(super) const SCHEMA_MAPS: &[&str] = &[
+    "properties",
+    "patternProperties",
+    "schemas",
+    "definitions",
+    "$defs",
+];
+
+/// Keys whose values are data, not schemas.
+const DATA_KEYS: &[&str] = &["default", "enum", "const", "example", "examples"];
+
+/// Whether the value under `key` is data rather than schemas. Extensions are, except for the
+/// bundled external documents.
+pub(super) fn is_data(key: &str) -> bool {
+    DATA_KEYS.contains(&key) || (key.starts_with("x-") && key != EXTERNAL_DOCUMENTS_KEY)
+}
+
+/// Rewrites `const: value` into `enum: [value]`, which describes the same set of values but is
+/// kept by [`openapiv3`], unlike `const`.
+pub fn const_to_enum(document: &mut Value) {
+    rewrite_const(document, None);
+}
+
+fn rewrite_const(value: &mut Value, parent_key: Option<&str>) {
+    match value {
+        Value::Object(object) => {
+            let is_schema_map = parent_key.is_some_and(|key| SCHEMA_MAPS.contains(&key));
+            if !is_schema_map
+                && !object.contains_key("enum")
+                && let Some(constant) = object.remove("const")
+            {
+                object.insert("enum".to_string(), Value::Array(vec![constant]));
+            }
+
+            for (key, value) in object.iter_mut() {
+                if !is_schema_map && is_data(key) {
+                    continue;
+                }
+                rewrite_const(value, Some(key));
+            }
+        }
+        Value::Array(array) => {
+            for value in array {
+                rewrite_const(value, parent_key);
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_const_becomes_single_value_enum() {
+        let mut document = serde_json::json!({
+            "components": {
+                "schemas": {
+                    "Kind": { "type": "string", "const": "cat" },
+                    "Pet": {
+                        "type": "object",
+                        "properties": {
+                            "const": { "type": "string" },
+                            "kind": { "const": "dog" }
+                        },
+                        "example": { "const": 1 }
+                    }
+                }
+            }
+        });
+
+        const_to_enum(&mut document);
+
+        assert_eq!(
+            document,
+            serde_json::json!({
+                "components": {
+                    "schemas": {
+                        "Kind": { "type": "string", "enum": ["cat"] },
+                        "Pet": {
+                            "type": "object",
+                            "properties": {
+                                "const": { "type": "string" },
+                                "kind": { "enum": ["dog"] }
+                            },
+                            "example": { "const": 1 }
+                        }
+                    }
+                }
+            })
+        );
+    }
+}
@@ -0,0 +1,411 @@
+//! Normalization of OpenAPI 3.1 documents into the OpenAPI 3.0 model of [`openapiv3`].
+//!
+//! OpenAPI 3.1 schemas are JSON Schema 2020-12, which expresses some things differently than the
+//! OpenAPI 3.0 schema dialect, e.g. `type: [string, "null"]` instead of `nullable: true`. These are
+//! rewritten into their 3.0 equivalents, what has no equivalent (e.g. `webhooks`) is dropped.
+//! `const` is handled for every version by [`super::const_to_enum`].
+
+use serde_json::{Map, Value, json};
+
+use super::normalize::{SCHEMA_MAPS, is_data};
+use super::pointer::{parse_fragment, to_reference};
+
+/// Rewrites an OpenAPI 3.1 document into an OpenAPI 3.0 one, other documents are left as is.
+pub fn downgrade_openapi_31(document: &mut Value) {
+    let is_31 = document
+        .get("openapi")
+        .and_then(Value::as_str)
+        .is_some_and(|version| version.starts_with("3.1"));
+    if !is_31 {
+        return;
+    }
+
+    if let Some(object) = document.as_object_mut()
+        && object.remove("webhooks").is_some()
+    {
+        log::warn!("Webhooks are not supported, ignoring them");
+    }
+
+    hoist_defs(document);
+    rewrite_schemas(document, None);
+}
+
+fn rewrite_schemas(value: &mut Value, parent_key: Option<&str>) {
+    match value {
+        Value::Object(object) => {
+            let is_schema_map = parent_key.is_some_and(|key| SCHEMA_MAPS.contains(&key));
+            if !is_schema_map {
+                rewrite_schema(object);
+            }
+
+            for (key, value) in object.iter_mut() {
+                if !is_schema_map && is_data(key) {
+                    continue;
+                }
+                rewrite_schemas(value, Some(key));
+            }
+        }
+        Value::Array(array) => {
+            for value in array {
+                rewrite_schemas(value, parent_key);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Rewrites the 3.1 keywords of an object that may be a schema. Only values that are invalid in
+/// 3.0 are touched, so objects that aren't schemas are left alone.
+fn rewrite_schema(object: &mut Map<String, Value>) {
+    // `type: [string, "null"]` becomes `type: string, nullable: true`
+    if let Some(Value::Array(types)) = object.get("type") {
+        let nullable = types.iter().any(|type_| type_ == "null");
+        let mut types: Vec<_> = types
+            .iter()
+            .filter(|type_| *type_ != "null")
+            .cloned()
+            .collect();
+        object.remove("type");
+        match types.len() {
+            0 => {}
+            1 => {
+                object.insert("type".to_string(), types.remove(0));
+            }
+            _ if !object.contains_key("anyOf") => {
+                let variants = types
+                    .into_iter()
+                    .map(|type_| json!({ "type": type_ }))
+                    .collect();
+                object.insert("anyOf".to_string(), Value::Array(variants));
+            }
+            _ => log::warn!("Schema with several types and anyOf, accepting any type"),
+        }
+        if nullable {
+            object.insert("nullable".to_string(), Value::Bool(true));
+        }
+    } else if object.get("type").is_some_and(|type_| type_ == "null") {
+        object.remove("type");
+        object.insert("nullable".to_string(), Value::Bool(true));
+    }
+
+    // `oneOf: [{ $ref: ... }, { type: "null" }]` becomes `allOf: [{ $ref: ... }], nullable: true`
+    for key in ["oneOf", "anyOf"] {
+        let Some(Value::Array(variants)) = object.get_mut(key) else {
+            continue;
+        };
+        let count = variants.len();
+        variants.retain(|variant| !is_null_schema(variant));
+        if variants.len() == count {
+            continue;
+        }
+        object.insert("nullable".to_string(), Value::Bool(true));
+        if let Some(Value::Array(variants)) = object.get(key)
+            && variants.len() == 1
+            && !object.contains_key("allOf")
+        {
+            let variants = object.remove(key).unwrap_or_default();
+            object.insert("allOf".to_string(), variants);
+        }
+    }
+
+    // `examples: [...]` becomes `example`, 3.0 has `examples` maps outside of schemas only
+    if let Some(Value::Array(_)) = object.get("examples")
+        && let Some(Value::Array(examples)) = object.remove("examples")
+        && !object.contains_key("example")
+        && let Some(example) = examples.into_iter().next()
+    {
+        object.insert("example".to_string(), example);
+    }
+
+    // `exclusiveMinimum: 1` becomes `minimum: 1, exclusiveMinimum: true`, unless `minimum` is the
+    // tighter bound, which is kept as it is
+    for (exclusive, bound, is_tighter) in [
+        (
+            "exclusiveMinimum",
+            "minimum",
+            f64::gt as fn(&f64, &f64) -> bool,
+        ),
+        ("exclusiveMaximum", "maximum", f64::lt),
+    ] {
+        let Some(Value::Number(limit)) = object.get(exclusive) else {
+            continue;
+        };
+        let limit = limit.clone();
+        if let (Some(inclusive), Some(exclusive_limit)) =
+            (object.get(bound).and_then(Value::as_f64), limit.as_f64())
+            && is_tighter(&inclusive, &exclusive_limit)
+        {
+            object.shift_remove(exclusive);
+            continue;
+        }
+        object.insert(bound.to_string(), Value::Number(limit));
+        object.insert(exclusive.to_string(), Value::Bool(true));
+    }
+
+    // `contentEncoding` and `contentMediaType` replace the `byte` and `binary` formats
+    if object.get("type").is_some_and(|type_| type_ == "string") && !object.contains_key("format") {
+        let format = match object.get("contentEncoding") {
+            Some(encoding) if encoding == "base64" => Some("byte"),
+            Some(_) => None,
+            None => object.contains_key("contentMediaType").then_some("binary"),
+        };
+        if let Some(format) = format {
+            object.insert("format".to_string(), Value::String(format.to_string()));
+        }
+    }
+}
+
+fn is_null_schema(schema: &Value) -> bool {
+    match schema.get("type") {
+        Some(Value::Array(types)) => types.iter().all(|type_| type_ == "null"),
+        Some(type_) => type_ == "null",
+        None => false,
+    }
+}
+
+/// Moves the schemas of `$defs`, which [`openapiv3`] drops, into `components/schemas` and points
+/// the references to them there.
+fn hoist_defs(document: &mut Value) {
+    let mut defs = Vec::new();
+    take_defs(document, &mut Vec::new(), None, &mut defs);
+    if defs.is_empty() {
+        return;
+    }
+
+    let Some(root) = document.as_object_mut() else {
+        return;
+    };
+    let schemas = root
+        .entry("components")
+        .or_insert_with(|| json!({}))
+        .as_object_mut()
+        .and_then(|components| {
+            components
+                .entry("schemas")
+                .or_insert_with(|| json!({}))
+                .as_object_mut()
+        });
+    let Some(schemas) = schemas else {
+        return;
+    };
+
+    let mut moved = Vec::new();
+    for (pointer, schema) in defs {
+        let def_name = pointer.last().cloned().unwrap_or_default();
+        let name = (1..)
+            .map(|count| match count {
+                1 => def_name.clone(),
+                _ => format!("{def_name}_{count}"),
+            })
+            .find(|name| !schemas.contains_key(name))
+            .expect("a free schema name");
+        schemas.insert(name.clone(), schema);
+        moved.push((
+            pointer,
+            vec!["components".to_string(), "schemas".to_string(), name],
+        ));
+    }
+
+    rewrite_refs(document, &moved);
+}
+
+/// Removes the `$defs` below `value`, which is at `pointer`, collecting the pointers their
+/// schemas were at. Nested `$defs` are collected too.
+fn take_defs(
+    value: &mut Value,
+    pointer: &mut Vec<String>,
+    parent_key: Option<&str>,
+    defs: &mut Vec<(Vec<String>, Value)>,
+) {
+    match value {
+        Value::Object(object) => {
+            let is_schema_map = parent_key.is_some_and(|key| SCHEMA_MAPS.contains(&key));
+            if !is_schema_map && let Some(Value::Object(schemas)) = object.remove("$defs") {
+                for (name, mut schema) in schemas {
+                    pointer.extend(["$defs".to_string(), name]);
+                    take_defs(&mut schema, pointer, None, defs);
+                    defs.push((pointer.clone(), schema));
+                    pointer.truncate(pointer.len() - 2);
+                }
+            }
+
+            for (key, value) in object.iter_mut() {
+                if !is_schema_map && is_data(key) {
+                    continue;
+                }
+                pointer.push(key.clone());
+                take_defs(value, pointer, Some(key), defs);
+                pointer.pop();
+            }
+        }
+        Value::Array(array) => {
+            for (index, value) in array.iter_mut().enumerate() {
+                pointer.push(index.to_string());
+                take_defs(value, pointer, parent_key, defs);
+                pointer.pop();
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Points the references into the `moved` schemas to their new place.
+fn rewrite_refs(value: &mut Value, moved: &[(Vec<String>, Vec<String>)]) {
+    match value {
+        Value::Object(object) => {
+            for (key, value) in object.iter_mut() {
+                match value {
+                    Value::String(reference) if key == "$ref" => {
+                        let Some(fragment) = reference.strip_prefix('#') else {
+                            continue;
+                        };
+                        let Ok(segments) = parse_fragment(fragment) else {
+                            continue;
+                        };
+                        // the longest match, nested `$defs` moved separately from their parent
+                        let target = moved
+                            .iter()
+                            .filter(|(from, _)| segments.starts_with(from))
+                            .max_by_key(|(from, _)| from.len());
+                        if let Some((from, to)) = target {
+                            let mut segments_to = to.clone();
+                            segments_to.extend_from_slice(&segments[from.len()..]);
+                            *reference = to_reference(&segments_to);
+                        }
+                    }
+                    _ => rewrite_refs(value, moved),
+                }
+            }
+        }
+        Value::Array(array) => {
+            for value in array {
+                rewrite_refs(value, moved);
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_nullable_types() {
+        let mut document = json!({
+            "openapi": "3.1.0",
+            "components": {
+                "schemas": {
+                    "Name": { "type": ["string", "null"], "examples": ["Rex"] },
+                    "Id": { "type": ["string", "integer"] },
+                    "Owner": {
+                        "oneOf": [{ "$ref": "#/components/schemas/Name" }, { "type": "null" }]
+                    },
+                    "Age": { "type": "integer", "exclusiveMinimum": 0 },
+                    "Score": {
+                        "type": "number",
+                        "minimum": 5,
+                        "exclusiveMinimum": 0,
+                        "maximum": 10,
+                        "exclusiveMaximum": 20
+                    },
+                    "Ratio": {
+                        "type": "number",
+                        "minimum": 0,
+                        "exclusiveMinimum": 0,
+                        "maximum": 1,
+                        "exclusiveMaximum": 0.5
+                    },
+                    "Photo": { "type": "string", "contentMediaType": "image/png" }
+                }
+            },
+            "webhooks": { "newPet": {} }
+        });
+
+        downgrade_openapi_31(&mut document);
+
+        assert_eq!(
+            document,
+            json!({
+                "openapi": "3.1.0",
+                "components": {
+                    "schemas": {
+                        "Name": { "type": "string", "nullable": true, "example": "Rex" },
+                        "Id": { "anyOf": [{ "type": "string" }, { "type": "integer" }] },
+                        "Owner": {
+                            "allOf": [{ "$ref": "#/components/schemas/Name" }],
+                            "nullable": true
+                        },
+                        "Age": { "type": "integer", "minimum": 0, "exclusiveMinimum": true },
+                        "Score": { "type": "number", "minimum": 5, "maximum": 10 },
+                        "Ratio": {
+                            "type": "number",
+                            "minimum": 0,
+                            "exclusiveMinimum": true,
+                            "maximum": 0.5,
+                            "exclusiveMaximum": true
+                        },
+                        "Photo": {
+                            "type": "string",
+                            "contentMediaType": "image/png",
+                            "format": "binary"
+                        }
+                    }
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn test_defs_are_moved_to_components() {
+        let mut document = json!({
+            "openapi": "3.1.0",
+            "components": {
+                "schemas": {
+                    "Tag": { "type": "string" },
+                    "Pet": {
+                        "type": "object",
+                        "properties": {
+                            "tag": { "$ref": "#/components/schemas/Pet/$defs/Tag" },
+                            "owner": { "$ref": "#/components/schemas/Pet/$defs/Owner/properties/name" }
+                        },
+                        "$defs": {
+                            "Tag": { "type": "integer" },
+                            "Owner": {
+                                "type": "object",
+                                "properties": { "name": { "type": "string" } }
+                            }
+                        }
+                    }
+                }
+            }
+        });
+
+        downgrade_openapi_31(&mut document);
+
+        let schemas = &document["components"]["schemas"];
+        assert!(schemas["Pet"].get("$defs").is_none());
+        assert_eq!(schemas["Tag_2"], json!({ "type": "integer" }));
+        assert_eq!(
+            schemas["Pet"]["properties"]["tag"]["$ref"],
+            "#/components/schemas/Tag_2"
+        );
+        assert_eq!(
+            schemas["Pet"]["properties"]["owner"]["$ref"],
+            "#/components/schemas/Owner/properties/name"
+        );
+    }
+
+    #[test]
+    fn test_openapi_30_is_untouched() {
+        let mut document = json!({
+            "openapi": "3.0.3",
+            "components": { "schemas": { "Name": { "type": ["string", "null"] } } }
+        });
+        let original = document.clone();
+
+        downgrade_openapi_31(&mut document);
+
+        assert_eq!(document, original);
+    }
+}
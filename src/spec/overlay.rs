@@ -0,0 +1,235 @@
+//! Application of [OpenAPI Overlay](https://spec.openapis.org/overlay/v1.0.0.html) documents.
+//!
+//! An overlay is a list of actions, each selecting parts of the spec with a JSONPath `target` and
+//! either merging an `update` into them or removing them. Overlays keep changes to a vendor spec
+//! separate from it, so they can be applied again to the next version of the spec.
+
+use serde_json::Value;
+
+use super::json_path::{JsonPath, Location, Step};
+
+/// Applies the actions of `overlay` to `document`, in order.
+///
+/// Objects selected by an action are merged with its `update`, arrays get the `update` appended
+/// and other values are replaced by it. Actions whose target selects nothing are logged as a
+/// warning, since they usually mean that the spec changed under the overlay.
+pub fn apply_overlay(document: &mut Value, overlay: &Value) -> anyhow::Result<()> {
+    let version = overlay
+        .get("overlay")
+        .and_then(Value::as_str)
+        .ok_or_else(|| anyhow::anyhow!("Overlay must have an overlay version"))?;
+    anyhow::ensure!(
+        version.starts_with("1."),
+        "Only Overlay 1.x documents are supported, got {}",
+        version
+    );
+    let Some(Value::Array(actions)) = overlay.get("actions") else {
+        anyhow::bail!("Overlay must have a list of actions");
+    };
+
+    for action in actions {
+        let target = action
+            .get("target")
+            .and_then(Value::as_str)
+            .ok_or_else(|| anyhow::anyhow!("Overlay action must have a target"))?;
+        let locations = JsonPath::parse(target)?.select(document);
+        if locations.is_empty() {
+            let description = action.get("description").and_then(Value::as_str);
+            log::warn!(
+                "Overlay action target {} matched nothing{}{}",
+                target,
+                description.map_or(String::new(), |d| format!(" ({d})")),
+                if has_external_refs(document) {
+                    ", the parts of the spec in other files aren't overlaid yet"
+                } else {
+                    ""
+                }
+            );
+        }
+
+        let remove = action.get("remove").and_then(Value::as_bool) == Some(true);
+        match action.get("update") {
+            _ if remove => remove_all(document, locations),
+            Some(update) => {
+                for location in locations {
+                    if let Some(value) = lookup_mut(document, &location) {
+                        apply_update(value, update);
+                    }
+                }
+            }
+            None => anyhow::bail!(
+                "Overlay action for {} must have an update or remove: true",
+                target
+            ),
+        }
+    }
+    Ok(())
+}
+
+/// Whether the document references other files, which are only bundled after the overlays.
+fn has_external_refs(value: &Value) -> bool {
+    match value {
+        Value::Object(object) => object.iter().any(|(key, value)| match value {
+            Value::String(reference) if key == "$ref" => !reference.starts_with('#'),
+            value => has_external_refs(value),
+        }),
+        Value::Array(array) => array.iter().any(has_external_refs),
+        _ => false,
+    }
+}
+
+fn apply_update(value: &mut Value, update: &Value) {
+    match value {
+        Value::Array(array) => array.push(update.clone()),
+        _ => merge(value, update),
+    }
+}
+
+/// Merges `update` into `value`: objects recursively, other values are replaced.
+fn merge(value: &mut Value, update: &Value) {
+    match (value, update) {
+        (Value::Object(object), Value::Object(update)) => {
+            for (key, update) in update {
+                match object.get_mut(key) {
+                    Some(value) => merge(value, update),
+                    None => {
+                        object.insert(key.clone(), update.clone());
+                    }
+                }
+            }
+        }
+        (value, update) => *value = update.clone(),
+    }
+}
+
+fn remove_all(document: &mut Value, mut locations: Vec<Location>) {
+    // the last elements of an array first, so the indices of the others stay valid
+    locations.sort_unstable_by(|a, b| b.cmp(a));
+    for mut location in locations {
+        let Some(step) = location.pop() else {
+            log::warn!("Overlay can't remove the whole document, ignoring the action");
+            continue;
+        };
+        match (lookup_mut(document, &location), step) {
+            (Some(Value::Object(object)), Step::Key(key)) => {
+                object.shift_remove(&key);
+            }
+            (Some(Value::Array(array)), Step::Index(index)) if index < array.len() => {
+                array.remove(index);
+            }
+            _ => {}
+        }
+    }
+}
+
+fn lookup_mut<'a>(value: &'a mut Value, location: &[Step]) -> Option<&'a mut Value> {
+    location.iter().try_fold(value, |value, step| match step {
+        Step::Key(key) => value.get_mut(key),
+        Step::Index(index) => value.get_mut(index),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn spec() -> Value {
+        json!({
+            "openapi": "3.0.3",
+            "info": { "title": "Pets", "version": "1.0.0" },
+            "paths": {
+                "/pets": {
+                    "get": {
+                        "operationId": "listPets",
+                        "tags": ["pets"],
+                        "parameters": [
+                            { "name": "limit", "in": "query" },
+                            { "name": "X-Internal", "in": "header" },
+                            { "name": "X-Trace", "in": "header" }
+                        ]
+                    },
+                    "delete": { "operationId": "deleteAllPets", "tags": ["admin"] }
+                }
+            }
+        })
+    }
+
+    #[test]
+    fn test_update_and_remove() {
+        let mut document = spec();
+        let overlay = json!({
+            "overlay": "1.0.0",
+            "info": { "title": "Fixes", "version": "1.0.0" },
+            "actions": [
+                {
+                    "target": "$.info",
+                    "update": { "title": "Pet Store", "x-logo": { "url": "logo.png" } }
+                },
+                {
+                    "target": "$.paths['/pets'].get",
+                    "update": { "description": "Lists the pets" }
+                },
+                { "target": "$.paths['/pets'].get.tags", "update": "store" },
+                {
+                    "target": "$.paths.*.get.parameters[?(@.in == 'header')]",
+                    "remove": true
+                },
+                {
+                    "target": "$.paths.*[?@.tags[0] == 'admin']",
+                    "description": "Not for agents",
+                    "remove": true
+                }
+            ]
+        });
+
+        apply_overlay(&mut document, &overlay).unwrap();
+
+        assert_eq!(
+            document["info"],
+            json!({ "title": "Pet Store", "version": "1.0.0", "x-logo": { "url": "logo.png" } })
+        );
+        assert_eq!(
+            document["paths"]["/pets"],
+            json!({
+                "get": {
+                    "operationId": "listPets",
+                    "tags": ["pets", "store"],
+                    "parameters": [{ "name": "limit", "in": "query" }],
+                    "description": "Lists the pets"
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn test_unmatched_target_is_ignored() {
+        let mut document = spec();
+        let overlay = json!({
+            "overlay": "1.0.0",
+            "actions": [{ "target": "$.paths['/owners']", "remove": true }]
+        });
+
+        apply_overlay(&mut document, &overlay).unwrap();
+
+        assert_eq!(document, spec());
+        assert!(!has_external_refs(&document));
+        document["paths"]["/owners"] = json!({ "$ref": "./paths/owners.yaml" });
+        assert!(has_external_refs(&document));
+    }
+
+    #[test]
+    fn test_invalid_overlays() {
+        let mut document = spec();
+        for overlay in [
+            json!({ "actions": [] }),
+            json!({ "overlay": "2.0.0", "actions": [] }),
+            json!({ "overlay": "1.0.0" }),
+            json!({ "overlay": "1.0.0", "actions": [{ "remove": true }] }),
+            json!({ "overlay": "1.0.0", "actions": [{ "target": "$.info" }] }),
+            json!({ "overlay": "1.0.0", "actions": [{ "target": "info", "remove": true }] }),
+        ] {
+            assert!(apply_overlay(&mut document, &overlay).is_err());
+        }
+    }
+}
@@ -0,0 +1,123 @@
+//! Inlining of path items that are references.
+//!
+//! Path items can be references to `components/pathItems` (OpenAPI 3.1), to other files, or to
+//! other entries of `paths`. [`openapiv3`] drops `components/pathItems`, so the references are
+//! replaced by the path items they point to before the document is deserialized.
+
+use serde_json::Value;
+
+use super::pointer::{lookup, parse_fragment, split_reference};
+
+/// Maximum number of references followed when a path item points to another reference.
+const MAX_REFERENCE_CHAIN: usize = 32;
+
+/// Replaces the path items of `paths` that are references by their targets. Fields next to the
+/// `$ref` (e.g. `summary`) take precedence over the ones of the target.
+pub fn inline_path_item_refs(document: &mut Value) -> anyhow::Result<()> {
+    let Some(Value::Object(paths)) = document.get("paths") else {
+        return Ok(());
+    };
+
+    let mut inlined = Vec::new();
+    for (path, path_item) in paths {
+        if path_item.get("$ref").is_some() {
+            inlined.push((path.clone(), resolve(document, path_item)?));
+        }
+    }
+
+    if let Some(Value::Object(paths)) = document.get_mut("paths") {
+        for (path, path_item) in inlined {
+            paths.insert(path, path_item);
+        }
+    }
+    Ok(())
+}
+
+fn resolve(document: &Value, path_item: &Value) -> anyhow::Result<Value> {
+    let mut path_item = path_item.clone();
+    for _ in 0..MAX_REFERENCE_CHAIN {
+        let Value::Object(mut fields) = path_item else {
+            anyhow::bail!("Path item must be an object");
+        };
+        let Some(reference) = fields.remove("$ref") else {
+            return Ok(Value::Object(fields));
+        };
+        let reference = reference
+            .as_str()
+            .ok_or_else(|| anyhow::anyhow!("Path item reference must be a string"))?;
+
+        let (target_document, fragment) = split_reference(reference);
+        anyhow::ensure!(
+            target_document.is_empty(),
+            "Reference to another document was not bundled: {}",
+            reference
+        );
+        let Some(Value::Object(target)) = lookup(document, &parse_fragment(fragment)?) else {
+            anyhow::bail!("Path item reference not found: {}", reference);
+        };
+
+        let mut merged = target.clone();
+        merged.extend(fields);
+        path_item = Value::Object(merged);
+    }
+    anyhow::bail!(
+        "Path item reference chain exceeds {} references, it is probably circular",
+        MAX_REFERENCE_CHAIN
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_inline_path_item_refs() {
+        let mut document = serde_json::json!({
+            "paths": {
+                "/users": { "$ref": "#/components/pathItems/Users", "summary": "All users" },
+                "/people": { "$ref": "#/paths/~1users" },
+                "/files": { "$ref": "#/x-openapi2mcp-external-documents/files.yaml" }
+            },
+            "components": {
+                "pathItems": {
+                    "Users": { "summary": "Users", "get": { "responses": {} } }
+                }
+            },
+            "x-openapi2mcp-external-documents": {
+                "files.yaml": { "post": { "responses": {} } }
+            }
+        });
+
+        inline_path_item_refs(&mut document).unwrap();
+
+        let paths = &document["paths"];
+        assert_eq!(
+            paths["/users"],
+            serde_json::json!({ "summary": "All users", "get": { "responses": {} } })
+        );
+        assert_eq!(
+            paths["/people"]["get"],
+            serde_json::json!({ "responses": {} })
+        );
+        assert_eq!(
+            paths["/files"],
+            serde_json::json!({ "post": { "responses": {} } })
+        );
+    }
+
+    #[test]
+    fn test_missing_and_circular_path_item_refs() {
+        let mut missing = serde_json::json!({
+            "paths": { "/users": { "$ref": "#/components/pathItems/Users" } }
+        });
+        assert!(inline_path_item_refs(&mut missing).is_err());
+
+        let mut circular = serde_json::json!({
+            "paths": {
+                "/a": { "$ref": "#/paths/~1b" },
+                "/b": { "$ref": "#/paths/~1a" }
+            }
+        });
+        assert!(inline_path_item_refs(&mut circular).is_err());
+    }
+}
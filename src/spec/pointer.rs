@@ -0,0 +1,114 @@
+//! JSON Pointer ([RFC 6901](https://www.rfc-editor.org/rfc/rfc6901)) helpers for `$ref` values.
+
+/// Splits a reference into the document it points to and its fragment, e.g.
+/// `common.yaml#/components/schemas/Error` into `("common.yaml", "/components/schemas/Error")`.
+/// The document is empty for same-document references.
+pub fn split_reference(reference: &str) -> (&str, &str) {
+    match reference.split_once('#') {
+        Some((document, fragment)) => (document, fragment),
+        None => (reference, ""),
+    }
+}
+
+/// Parses the fragment of a reference into unescaped pointer segments. The fragment is
+/// percent-decoded first, then `~1` and `~0` are unescaped in each segment.
+pub fn parse_fragment(fragment: &str) -> anyhow::Result<Vec<String>> {
+    let fragment = percent_decode(fragment)?;
+    if fragment.is_empty() {
+        return Ok(Vec::new());
+    }
+    let Some(fragment) = fragment.strip_prefix('/') else {
+        anyhow::bail!("Invalid JSON pointer: {}", fragment);
+    };
+    Ok(fragment.split('/').map(unescape).collect())
+}
+
+/// Builds a same-document reference from pointer segments.
+pub fn to_reference<S: AsRef<str>>(segments: &[S]) -> String {
+    let mut reference = String::from("#");
+    for segment in segments {
+        reference.push('/');
+        reference.push_str(&escape(segment.as_ref()));
+    }
+    reference
+}
+
+/// Looks up the value that the pointer segments point to.
+pub fn lookup<'a, S: AsRef<str>>(
+    value: &'a serde_json::Value,
+    segments: &[S],
+) -> Option<&'a serde_json::Value> {
+    segments
+        .iter()
+        .try_fold(value, |value, segment| match value {
+            serde_json::Value::Object(object) => object.get(segment.as_ref()),
+            serde_json::Value::Array(array) => array.get(segment.as_ref().parse::<usize>().ok()?),
+            _ => None,
+        })
+}
+
+pub fn escape(segment: &str) -> String {
+    segment.replace('~', "~0").replace('/', "~1")
+}
+
+fn unescape(segment: &str) -> String {
+    segment.replace("~1", "/").replace("~0", "~")
+}
+
+fn percent_decode(s: &str) -> anyhow::Result<String> {
+    let bytes = s.as_bytes();
+    let mut output = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' {
+            let hex = s
+                .get(i + 1..i + 3)
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+                .ok_or_else(|| anyhow::anyhow!("Invalid percent-encoding in reference: {}", s))?;
+            output.push(hex);
+            i += 3;
+        } else {
+            output.push(bytes[i]);
+            i += 1;
+        }
+    }
+    Ok(String::from_utf8(output)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_escaped_segments() {
+        let segments = parse_fragment("/paths/~1users~1{id}/get/a~0b").unwrap();
+        assert_eq!(segments, vec!["paths", "/users/{id}", "get", "a~b"]);
+    }
+
+    #[test]
+    fn parse_percent_encoded_segments() {
+        let segments = parse_fragment("/paths/~1users~1%7Bid%7D").unwrap();
+        assert_eq!(segments, vec!["paths", "/users/{id}"]);
+    }
+
+    #[test]
+    fn escape_round_trip() {
+        let reference = to_reference(&["paths", "/users/{id}", "a~b"]);
+        assert_eq!(reference, "#/paths/~1users~1{id}/a~0b");
+        let (_, fragment) = split_reference(&reference);
+        assert_eq!(
+            parse_fragment(fragment).unwrap(),
+            vec!["paths", "/users/{id}", "a~b"]
+        );
+    }
+
+    #[test]
+    fn lookup_into_arrays() {
+        let value = serde_json::json!({ "a": [{ "b": 1 }, { "b": 2 }] });
+        assert_eq!(
+            lookup(&value, &["a", "1", "b"]),
+            Some(&serde_json::json!(2))
+        );
+        assert_eq!(lookup(&value, &["a", "2"]), None);
+    }
+}
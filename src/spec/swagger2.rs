@@ -0,0 +1,797 @@
+//! Conversion of Swagger 2.0 documents into OpenAPI 3.0.
+//!
+//! The parts of Swagger 2.0 that moved in OpenAPI 3.0 are rewritten into their new form:
+//! `host`, `basePath` and `schemes` become `servers`, the top-level `definitions`, `parameters`,
+//! `responses` and `securityDefinitions` move to `components`, `body` and `formData` parameters
+//! become request bodies, and `consumes` and `produces` become the media types of the request
+//! bodies and responses. What can't be expressed in OpenAPI 3.0 is reported as a warning.
+
+use serde_json::{Map, Value, json};
+
+use super::normalize::{SCHEMA_MAPS, is_data};
+
+const METHODS: &[&str] = &["get", "put", "post", "delete", "options", "head", "patch"];
+
+/// Keywords of Swagger 2.0 parameters, headers and items that OpenAPI 3.0 moved into `schema`.
+const SCHEMA_KEYWORDS: &[&str] = &[
+    "type",
+    "format",
+    "items",
+    "default",
+    "maximum",
+    "exclusiveMaximum",
+    "minimum",
+    "exclusiveMinimum",
+    "maxLength",
+    "minLength",
+    "pattern",
+    "maxItems",
+    "minItems",
+    "uniqueItems",
+    "enum",
+    "multipleOf",
+    "x-nullable",
+];
+
+/// Rewrites a Swagger 2.0 document into an OpenAPI 3.0 one, other documents are left as is.
+/// What can't be converted without loss is logged as a warning.
+pub fn upgrade_swagger_2(document: &mut Value) -> anyhow::Result<()> {
+    for note in upgrade(document)? {
+        log::warn!("Swagger 2.0 conversion: {}", note);
+    }
+    Ok(())
+}
+
+/// Rewrites a Swagger 2.0 document, returning what couldn't be converted without loss.
+fn upgrade(document: &mut Value) -> anyhow::Result<Vec<String>> {
+    if document.get("swagger").and_then(Value::as_str) != Some("2.0") {
+        return Ok(Vec::new());
+    }
+    let Value::Object(root) = document else {
+        anyhow::bail!("Swagger document must be an object");
+    };
+
+    let mut upgrade = Upgrade {
+        parameters: match root.remove("parameters") {
+            Some(Value::Object(parameters)) => parameters,
+            _ => Map::new(),
+        },
+        consumes: media_types(root.remove("consumes")),
+        produces: media_types(root.remove("produces")),
+        notes: Vec::new(),
+    };
+
+    root.remove("swagger");
+    root.insert("openapi".to_string(), json!("3.0.3"));
+
+    let servers = servers(
+        root.remove("host"),
+        root.remove("basePath"),
+        root.remove("schemes"),
+    );
+    if !servers.is_empty() {
+        root.insert("servers".to_string(), Value::Array(servers));
+    }
+
+    let mut components = Map::new();
+    if let Some(definitions) = root.remove("definitions") {
+        components.insert("schemas".to_string(), definitions);
+    }
+    let (parameters, request_bodies) = upgrade.global_parameters();
+    if !parameters.is_empty() {
+        components.insert("parameters".to_string(), Value::Object(parameters));
+    }
+    if !request_bodies.is_empty() {
+        components.insert("requestBodies".to_string(), Value::Object(request_bodies));
+    }
+    if let Some(Value::Object(responses)) = root.remove("responses") {
+        let responses = responses
+            .into_iter()
+            .map(|(name, response)| {
+                let location = format!("#/responses/{name}");
+                let response = upgrade.response(response, &location, &upgrade.produces.clone());
+                (name, response)
+            })
+            .collect();
+        components.insert("responses".to_string(), Value::Object(responses));
+    }
+    if let Some(Value::Object(schemes)) = root.remove("securityDefinitions") {
+        let schemes = schemes
+            .into_iter()
+            .map(|(name, scheme)| {
+                let scheme = security_scheme(scheme, &name, &mut upgrade.notes);
+                (name, scheme)
+            })
+            .collect();
+        components.insert("securitySchemes".to_string(), Value::Object(schemes));
+    }
+    if !components.is_empty() {
+        root.insert("components".to_string(), Value::Object(components));
+    }
+
+    if let Some(Value::Object(paths)) = root.get_mut("paths") {
+        for (path, path_item) in paths.iter_mut() {
+            if let Value::Object(path_item) = path_item {
+                upgrade.path_item(path, path_item);
+            }
+        }
+    }
+
+    rewrite_schemas(document, None);
+
+    Ok(upgrade.notes)
+}
+
+struct Upgrade {
+    /// The top-level parameters, as they are in the Swagger document.
+    parameters: Map<String, Value>,
+    consumes: Vec<String>,
+    produces: Vec<String>,
+    /// What couldn't be converted without loss.
+    notes: Vec<String>,
+}
+
+impl Upgrade {
+    /// Splits the top-level parameters into OpenAPI 3.0 parameters and request bodies. `formData`
+    /// parameters are inlined into the request bodies of the operations that use them.
+    fn global_parameters(&mut self) -> (Map<String, Value>, Map<String, Value>) {
+        let mut parameters = Map::new();
+        let mut request_bodies = Map::new();
+        for (name, parameter) in self.parameters.clone() {
+            match parameter.get("in").and_then(Value::as_str) {
+                Some("body") => {
+                    let body = request_body(parameter, &self.consumes);
+                    request_bodies.insert(name, body);
+                }
+                Some("formData") => {}
+                _ => {
+                    let parameter = self.parameter(parameter, &format!("#/parameters/{name}"));
+                    parameters.insert(name, parameter);
+                }
+            }
+        }
+        (parameters, request_bodies)
+    }
+
+    fn path_item(&mut self, path: &str, path_item: &mut Map<String, Value>) {
+        // `body` and `formData` parameters of the path are moved to each of its operations
+        let mut shared = Vec::new();
+        if let Some(Value::Array(parameters)) = path_item.remove("parameters") {
+            let mut kept = Vec::new();
+            for parameter in parameters {
+                if self.is_missing(&parameter, path) {
+                    continue;
+                }
+                match self.location(&parameter).as_deref() {
+                    Some("body" | "formData") => shared.push(parameter),
+                    _ => kept.push(self.parameter_or_ref(parameter, path)),
+                }
+            }
+            if !kept.is_empty() {
+                path_item.insert("parameters".to_string(), Value::Array(kept));
+            }
+        }
+        if !shared.is_empty() {
+            self.notes.push(format!(
+                "body and formData parameters of path {path} are added to the request body of each of its operations"
+            ));
+        }
+
+        for method in METHODS {
+            if let Some(Value::Object(operation)) = path_item.get_mut(*method) {
+                let location = format!("{} {}", method.to_uppercase(), path);
+                self.operation(&location, operation, &shared);
+            }
+        }
+    }
+
+    fn operation(&mut self, location: &str, operation: &mut Map<String, Value>, shared: &[Value]) {
+        let consumes = match operation.remove("consumes") {
+            Some(consumes) => media_types(Some(consumes)),
+            None => self.consumes.clone(),
+        };
+        let produces = match operation.remove("produces") {
+            Some(produces) => media_types(Some(produces)),
+            None => self.produces.clone(),
+        };
+
+        let own = match operation.remove("parameters") {
+            Some(Value::Array(parameters)) => parameters,
+            _ => Vec::new(),
+        };
+        let mut parameters = Vec::new();
+        let mut form_fields = Vec::new();
+        let mut body = None;
+        for parameter in shared.iter().cloned().chain(own) {
+            if self.is_missing(&parameter, location) {
+                continue;
+            }
+            match self.location(&parameter).as_deref() {
+                Some("body") => {
+                    body = Some(match reference(&parameter) {
+                        Some(name) => {
+                            json!({ "$ref": format!("#/components/requestBodies/{name}") })
+                        }
+                        None => request_body(parameter, &consumes),
+                    });
+                }
+                Some("formData") => {
+                    let field = match reference(&parameter) {
+                        Some(name) => self.parameters.get(name).cloned().unwrap_or(parameter),
+                        None => parameter,
+                    };
+                    form_fields.push(field);
+                }
+                _ => parameters.push(self.parameter_or_ref(parameter, location)),
+            }
+        }
+        if !form_fields.is_empty() {
+            body = Some(self.form_body(location, form_fields, &consumes));
+        }
+
+        if !parameters.is_empty() {
+            operation.insert("parameters".to_string(), Value::Array(parameters));
+        }
+        if let Some(body) = body {
+            operation.insert("requestBody".to_string(), body);
+        }
+
+        if let Some(Value::Object(responses)) = operation.get_mut("responses") {
+            for (status, response) in responses.iter_mut() {
+                let location = format!("response {status} of {location}");
+                *response = self.response(response.take(), &location, &produces);
+            }
+        }
+    }
+
+    /// Whether the parameter refers to a top-level parameter that doesn't exist, which is dropped.
+    fn is_missing(&mut self, parameter: &Value, location: &str) -> bool {
+        let Some(name) = reference(parameter) else {
+            return false;
+        };
+        if self.parameters.contains_key(name) {
+            return false;
+        }
+        self.notes.push(format!(
+            "parameter #/parameters/{name} of {location} doesn't exist and is dropped"
+        ));
+        true
+    }
+
+    /// Where the parameter goes, looking up references to top-level parameters.
+    fn location(&self, parameter: &Value) -> Option<String> {
+        let parameter = match reference(parameter) {
+            Some(name) => self.parameters.get(name)?,
+            None => parameter,
+        };
+        parameter
+            .get("in")
+            .and_then(Value::as_str)
+            .map(str::to_string)
+    }
+
+    fn parameter_or_ref(&mut self, parameter: Value, location: &str) -> Value {
+        if parameter.get("$ref").is_some() {
+            return parameter;
+        }
+        self.parameter(parameter, location)
+    }
+
+    /// Moves the type of a non-body parameter into its `schema` and `collectionFormat` into
+    /// `style` and `explode`.
+    fn parameter(&mut self, parameter: Value, location: &str) -> Value {
+        let Value::Object(mut parameter) = parameter else {
+            return parameter;
+        };
+        self.note_ignored_fields(&parameter, "parameter", location);
+        let is_array = parameter.get("type").is_some_and(|type_| type_ == "array");
+        let collection_format = parameter.remove("collectionFormat");
+        let schema = schema_of(&mut parameter);
+        parameter.insert("schema".to_string(), schema);
+
+        if is_array {
+            let is_query = parameter.get("in").is_some_and(|in_| in_ == "query");
+            let name = parameter.get("name").and_then(Value::as_str).unwrap_or("");
+            let (style, explode) = match collection_format.as_ref().and_then(Value::as_str) {
+                None | Some("csv") if is_query => ("form", false),
+                Some("multi") if is_query => ("form", true),
+                Some("ssv") if is_query => ("spaceDelimited", false),
+                Some("pipes") if is_query => ("pipeDelimited", false),
+                None | Some("csv") => ("simple", false),
+                Some(format) => {
+                    self.notes.push(format!(
+                        "collectionFormat {format} of parameter {name} of {location} is not supported, using csv"
+                    ));
+                    if is_query {
+                        ("form", false)
+                    } else {
+                        ("simple", false)
+                    }
+                }
+            };
+            parameter.insert("style".to_string(), json!(style));
+            parameter.insert("explode".to_string(), json!(explode));
+        }
+        Value::Object(parameter)
+    }
+
+    /// Notes the fields of a parameter that OpenAPI 3.0 has no place for or that tools don't use.
+    fn note_ignored_fields(&mut self, parameter: &Map<String, Value>, kind: &str, location: &str) {
+        let name = parameter.get("name").and_then(Value::as_str).unwrap_or("");
+        for field in ["allowEmptyValue", "x-example"] {
+            if parameter.contains_key(field) {
+                self.notes
+                    .push(format!("{field} of {kind} {name} of {location} is ignored"));
+            }
+        }
+    }
+
+    /// Turns `formData` parameters into the properties of a form request body.
+    fn form_body(&mut self, location: &str, fields: Vec<Value>, consumes: &[String]) -> Value {
+        let has_file = fields
+            .iter()
+            .any(|field| field.get("type").is_some_and(|type_| type_ == "file"));
+        let media_type = match has_file || consumes.iter().any(|c| c == "multipart/form-data") {
+            true => "multipart/form-data",
+            false => "application/x-www-form-urlencoded",
+        };
+
+        let mut properties = Map::new();
+        let mut required = Vec::new();
+        for field in fields {
+            let Value::Object(mut field) = field else {
+                continue;
+            };
+            let name = field
+                .get("name")
+                .and_then(Value::as_str)
+                .unwrap_or_default()
+                .to_string();
+            self.note_ignored_fields(&field, "form field", location);
+            if let Some(format) = field.remove("collectionFormat")
+                && format != "multi"
+            {
+                self.notes.push(format!(
+                    "collectionFormat {format} of form field {name} of {location} is not supported, sending the items as separate fields"
+                ));
+            }
+            if field.get("required").is_some_and(|r| r == true) {
+                required.push(json!(name));
+            }
+            let mut schema = schema_of(&mut field);
+            if let (Some(description), Value::Object(schema)) =
+                (field.remove("description"), &mut schema)
+            {
+                schema.insert("description".to_string(), description);
+            }
+            properties.insert(name, schema);
+        }
+
+        let mut schema = json!({ "type": "object", "properties": properties });
+        if !required.is_empty() {
+            schema["required"] = Value::Array(required);
+        }
+        json!({ "content": { media_type: { "schema": schema } } })
+    }
+
+    fn response(&mut self, response: Value, location: &str, produces: &[String]) -> Value {
+        let Value::Object(mut response) = response else {
+            return response;
+        };
+        if response.contains_key("$ref") {
+            return Value::Object(response);
+        }
+        response.entry("description").or_insert_with(|| json!(""));
+
+        if let Some(Value::Object(headers)) = response.get_mut("headers") {
+            for header in headers.values_mut() {
+                if let Value::Object(header) = header {
+                    header.remove("collectionFormat");
+                    let schema = schema_of(header);
+                    header.insert("schema".to_string(), schema);
+                }
+            }
+        }
+
+        let schema = response.remove("schema");
+        let examples = match response.remove("examples") {
+            Some(Value::Object(examples)) => examples,
+            _ => Map::new(),
+        };
+        let mut content = Map::new();
+        if let Some(schema) = &schema {
+            for media_type in produces {
+                content.insert(media_type.clone(), json!({ "schema": schema }));
+            }
+        }
+        for (media_type, example) in examples {
+            if schema.is_some() && !content.contains_key(&media_type) {
+                self.notes.push(format!(
+                    "example for {media_type} of {location} is kept without a schema, {media_type} is not in produces"
+                ));
+            }
+            let media_type = content
+                .entry(media_type)
+                .or_insert_with(|| json!({}))
+                .as_object_mut()
+                .expect("media types are objects");
+            media_type.insert("example".to_string(), example);
+        }
+        if !content.is_empty() {
+            response.insert("content".to_string(), Value::Object(content));
+        }
+        Value::Object(response)
+    }
+}
+
+/// Name of the top-level parameter a parameter refers to.
+fn reference(parameter: &Value) -> Option<&str> {
+    parameter
+        .get("$ref")
+        .and_then(Value::as_str)
+        .and_then(|reference| reference.strip_prefix("#/parameters/"))
+}
+
+fn media_types(media_types: Option<Value>) -> Vec<String> {
+    let media_types: Vec<String> = match media_types {
+        Some(Value::Array(media_types)) => media_types
+            .into_iter()
+            .filter_map(|media_type| media_type.as_str().map(str::to_string))
+            .collect(),
+        _ => Vec::new(),
+    };
+    match media_types.is_empty() {
+        true => vec!["application/json".to_string()],
+        false => media_types,
+    }
+}
+
+fn servers(host: Option<Value>, base_path: Option<Value>, schemes: Option<Value>) -> Vec<Value> {
+    let base_path = base_path
+        .as_ref()
+        .and_then(Value::as_str)
+        .unwrap_or_default()
+        .trim_end_matches('/');
+    let Some(host) = host.as_ref().and_then(Value::as_str) else {
+        return match base_path.is_empty() {
+            true => Vec::new(),
+            false => vec![json!({ "url": base_path })],
+        };
+    };
+
+    let schemes = match schemes {
+        Some(Value::Array(schemes)) if !schemes.is_empty() => schemes,
+        _ => vec![json!("https")],
+    };
+    schemes
+        .iter()
+        .filter_map(Value::as_str)
+        .map(|scheme| json!({ "url": format!("{scheme}://{host}{base_path}") }))
+        .collect()
+}
+
+fn request_body(parameter: Value, consumes: &[String]) -> Value {
+    let schema = parameter
+        .get("schema")
+        .cloned()
+        .unwrap_or_else(|| json!({}));
+    let content: Map<_, _> = consumes
+        .iter()
+        .map(|media_type| (media_type.clone(), json!({ "schema": schema })))
+        .collect();
+
+    let mut body = json!({ "content": content });
+    if let Some(description) = parameter.get("description") {
+        body["description"] = description.clone();
+    }
+    if parameter.get("required").is_some_and(|r| r == true) {
+        body["required"] = json!(true);
+    }
+    body
+}
+
+/// Takes the schema keywords out of a parameter, header or items object.
+fn schema_of(object: &mut Map<String, Value>) -> Value {
+    let mut schema = Map::new();
+    for keyword in SCHEMA_KEYWORDS {
+        if let Some(value) = object.remove(*keyword) {
+            schema.insert(keyword.to_string(), value);
+        }
+    }
+    if let Some(Value::Object(items)) = schema.get_mut("items") {
+        items.remove("collectionFormat");
+        let items_schema = schema_of(items);
+        schema.insert("items".to_string(), items_schema);
+    }
+    Value::Object(schema)
+}
+
+fn security_scheme(scheme: Value, name: &str, notes: &mut Vec<String>) -> Value {
+    let Value::Object(mut scheme) = scheme else {
+        return scheme;
+    };
+    match scheme.get("type").and_then(Value::as_str) {
+        Some("basic") => {
+            scheme.insert("type".to_string(), json!("http"));
+            scheme.insert("scheme".to_string(), json!("basic"));
+        }
+        Some("oauth2") => {
+            let flow = scheme.remove("flow");
+            let mut flow_object = Map::new();
+            for key in ["authorizationUrl", "tokenUrl"] {
+                if let Some(url) = scheme.remove(key) {
+                    flow_object.insert(key.to_string(), url);
+                }
+            }
+            let scopes = scheme.remove("scopes").unwrap_or_else(|| json!({}));
+            flow_object.insert("scopes".to_string(), scopes);
+
+            let flow_name = match flow.as_ref().and_then(Value::as_str) {
+                Some("implicit") => "implicit",
+                Some("password") => "password",
+                Some("application") => "clientCredentials",
+                Some("accessCode") => "authorizationCode",
+                other => {
+                    notes.push(format!(
+                        "OAuth2 flow {} of security scheme {name} is unknown, using accessCode",
+                        other.unwrap_or("(missing)")
+                    ));
+                    "authorizationCode"
+                }
+            };
+            if flow_name != "authorizationCode" {
+                notes.push(format!(
+                    "OAuth2 flow {} of security scheme {name} is kept, but the generated server only supports the accessCode flow",
+                    flow.as_ref().and_then(Value::as_str).unwrap_or_default()
+                ));
+            }
+            scheme.insert(
+                "flows".to_string(),
+                json!({ flow_name: Value::Object(flow_object) }),
+            );
+        }
+        _ => {}
+    }
+    Value::Object(scheme)
+}
+
+/// Points the references to their new place under `components` and rewrites the schema keywords
+/// that changed.
+fn rewrite_schemas(value: &mut Value, parent_key: Option<&str>) {
+    match value {
+        Value::Object(object) => {
+            let is_schema_map = parent_key.is_some_and(|key| SCHEMA_MAPS.contains(&key));
+            if !is_schema_map {
+                rewrite_schema(object);
+            }
+
+            for (key, value) in object.iter_mut() {
+                if !is_schema_map && is_data(key) {
+                    continue;
+                }
+                rewrite_schemas(value, Some(key));
+            }
+        }
+        Value::Array(array) => {
+            for value in array {
+                rewrite_schemas(value, parent_key);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn rewrite_schema(object: &mut Map<String, Value>) {
+    if let Some(Value::String(reference)) = object.get_mut("$ref") {
+        for (from, to) in [
+            ("#/definitions/", "#/components/schemas/"),
+            ("#/parameters/", "#/components/parameters/"),
+            ("#/responses/", "#/components/responses/"),
+        ] {
+            if let Some(name) = reference.strip_prefix(from) {
+                *reference = format!("{to}{name}");
+            }
+        }
+    }
+
+    if object.get("type").is_some_and(|type_| type_ == "file") {
+        object.insert("type".to_string(), json!("string"));
+        object.insert("format".to_string(), json!("binary"));
+    }
+    if let Some(nullable) = object.remove("x-nullable") {
+        object.insert("nullable".to_string(), nullable);
+    }
+    if let Some(Value::String(property_name)) = object.get("discriminator") {
+        let discriminator = json!({ "propertyName": property_name });
+        object.insert("discriminator".to_string(), discriminator);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_upgrade_document() {
+        let mut document = json!({
+            "swagger": "2.0",
+            "info": { "title": "Pets", "version": "1.0" },
+            "host": "pets.example.com",
+            "basePath": "/v1",
+            "schemes": ["https", "http"],
+            "consumes": ["application/json"],
+            "produces": ["application/json"],
+            "parameters": {
+                "Limit": { "name": "limit", "in": "query", "type": "integer", "minimum": 1, "allowEmptyValue": true },
+                "NewPet": { "name": "pet", "in": "body", "schema": { "$ref": "#/definitions/Pet" } }
+            },
+            "securityDefinitions": {
+                "basic": { "type": "basic" },
+                "oauth": {
+                    "type": "oauth2",
+                    "flow": "accessCode",
+                    "authorizationUrl": "https://example.com/authorize",
+                    "tokenUrl": "https://example.com/token",
+                    "scopes": { "read": "Read pets" }
+                }
+            },
+            "paths": {
+                "/pets": {
+                    "get": {
+                        "parameters": [
+                            { "$ref": "#/parameters/Limit" },
+                            { "name": "tags", "in": "query", "type": "array", "items": { "type": "string" }, "collectionFormat": "pipes" }
+                        ],
+                        "responses": {
+                            "200": {
+                                "description": "The pets",
+                                "schema": { "type": "array", "items": { "$ref": "#/definitions/Pet" } },
+                                "examples": { "text/plain": "Rex" }
+                            }
+                        }
+                    },
+                    "post": {
+                        "parameters": [{ "$ref": "#/parameters/NewPet" }],
+                        "responses": { "201": { "description": "Created" } }
+                    }
+                }
+            },
+            "definitions": {
+                "Pet": {
+                    "type": "object",
+                    "discriminator": "kind",
+                    "properties": { "kind": { "type": "string" }, "tag": { "type": "string", "x-nullable": true } }
+                }
+            }
+        });
+
+        let notes = upgrade(&mut document).unwrap();
+
+        assert_eq!(
+            notes,
+            [
+                "allowEmptyValue of parameter limit of #/parameters/Limit is ignored",
+                "example for text/plain of response 200 of GET /pets is kept without a schema, text/plain is not in produces"
+            ]
+        );
+        assert_eq!(document["openapi"], "3.0.3");
+        assert!(document.get("swagger").is_none());
+        assert_eq!(
+            document["servers"],
+            json!([{ "url": "https://pets.example.com/v1" }, { "url": "http://pets.example.com/v1" }])
+        );
+
+        let components = &document["components"];
+        assert_eq!(
+            components["parameters"]["Limit"],
+            json!({
+                "name": "limit",
+                "in": "query",
+                "allowEmptyValue": true,
+                "schema": { "type": "integer", "minimum": 1 }
+            })
+        );
+        assert_eq!(
+            components["requestBodies"]["NewPet"]["content"]["application/json"]["schema"]["$ref"],
+            "#/components/schemas/Pet"
+        );
+        assert_eq!(
+            components["securitySchemes"]["basic"],
+            json!({ "type": "http", "scheme": "basic" })
+        );
+        assert_eq!(
+            components["securitySchemes"]["oauth"]["flows"]["authorizationCode"]["tokenUrl"],
+            "https://example.com/token"
+        );
+        assert_eq!(
+            components["schemas"]["Pet"]["discriminator"],
+            json!({ "propertyName": "kind" })
+        );
+        assert_eq!(
+            components["schemas"]["Pet"]["properties"]["tag"]["nullable"],
+            true
+        );
+
+        let get = &document["paths"]["/pets"]["get"];
+        assert_eq!(
+            get["parameters"][0]["$ref"],
+            "#/components/parameters/Limit"
+        );
+        assert_eq!(
+            get["parameters"][1],
+            json!({
+                "name": "tags",
+                "in": "query",
+                "schema": { "type": "array", "items": { "type": "string" } },
+                "style": "pipeDelimited",
+                "explode": false
+            })
+        );
+        assert_eq!(
+            get["responses"]["200"]["content"]["application/json"]["schema"]["items"]["$ref"],
+            "#/components/schemas/Pet"
+        );
+
+        let post = &document["paths"]["/pets"]["post"];
+        assert!(post.get("parameters").is_none());
+        assert_eq!(
+            post["requestBody"]["$ref"],
+            "#/components/requestBodies/NewPet"
+        );
+    }
+
+    #[test]
+    fn test_form_data_becomes_multipart_body() {
+        let mut document = json!({
+            "swagger": "2.0",
+            "info": { "title": "Files", "version": "1.0" },
+            "securityDefinitions": {
+                "oauth": { "type": "oauth2", "flow": "application", "tokenUrl": "https://example.com/token" }
+            },
+            "paths": {
+                "/files": {
+                    "parameters": [{ "name": "folder", "in": "formData", "type": "string" }],
+                    "post": {
+                        "consumes": ["multipart/form-data"],
+                        "parameters": [
+                            { "name": "file", "in": "formData", "type": "file", "required": true },
+                            { "name": "note", "in": "formData", "type": "string", "description": "A note", "x-example": "Hi" },
+                            { "$ref": "#/parameters/Missing" }
+                        ],
+                        "responses": { "200": { "description": "Uploaded" } }
+                    }
+                }
+            }
+        });
+
+        let notes = upgrade(&mut document).unwrap();
+
+        assert_eq!(
+            notes,
+            [
+                "OAuth2 flow application of security scheme oauth is kept, but the generated server only supports the accessCode flow",
+                "body and formData parameters of path /files are added to the request body of each of its operations",
+                "parameter #/parameters/Missing of POST /files doesn't exist and is dropped",
+                "x-example of form field note of POST /files is ignored"
+            ]
+        );
+        assert_eq!(
+            document["paths"]["/files"]["post"]["requestBody"],
+            json!({
+                "content": {
+                    "multipart/form-data": {
+                        "schema": {
+                            "type": "object",
+                            "properties": {
+                                "folder": { "type": "string" },
+                                "file": { "type": "string", "format": "binary" },
+                                "note": { "type": "string", "description": "A note" }
+                            },
+                            "required": ["file"]
+                        }
+                    }
+                }
+            })
+        );
+    }
+}
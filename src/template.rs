@@ -1,7 +1,11 @@
+use std::collections::{BTreeMap, HashSet};
 use std::fmt::Write;
 use std::path::Path;
 
+use convert_case::{Case, Casing};
+
 use crate::MCPServer;
+use crate::mcp_server::ServerVariable;
 
 // TODO: handle this as string instead of file
 pub fn update_tools_index_ts(
@@ -19,18 +23,19 @@ pub fn update_tools_index_ts(
     code.push_str("import { McpServer } from \"@modelcontextprotocol/sdk/server/mcp.js\";\n\n");
 
     // Import all generated tools
-    for tool in &server.tools {
+    let module_names = module_names(server.tools.iter().map(|tool| tool.name.as_str()));
+    for (tool, module_name) in server.tools.iter().zip(&module_names) {
         code.push_str(&format!(
             "import * as {} from \"./{}\";\n",
-            tool.name, tool.name
+            module_name, tool.name
         ));
     }
 
     code.push_str("\nexport function setupAllTools(server: McpServer) {\n");
 
     // Call setupTool for each tool
-    for tool in &server.tools {
-        code.push_str(&format!("  {}.setupTool(server);\n", tool.name));
+    for module_name in &module_names {
+        code.push_str(&format!("  {}.setupTool(server);\n", module_name));
     }
 
     code.push_str("}\n");
@@ -41,6 +46,30 @@ pub fn update_tools_index_ts(
     Ok(())
 }
 
+/// Identifiers the tools are imported as. Tool names can be reserved words, start with a digit or
+/// differ only in characters that aren't valid in identifiers, so they are prefixed, sanitized
+/// and given a numeric suffix when they collide.
+fn module_names<'a>(tool_names: impl IntoIterator<Item = &'a str>) -> Vec<String> {
+    let mut used = HashSet::new();
+    tool_names
+        .into_iter()
+        .map(|tool_name| {
+            let sanitized: String = tool_name
+                .chars()
+                .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+                .collect();
+            let base = format!("tool_{sanitized}");
+            let mut module_name = base.clone();
+            let mut suffix = 2;
+            while !used.insert(module_name.clone()) {
+                module_name = format!("{base}_{suffix}");
+                suffix += 1;
+            }
+            module_name
+        })
+        .collect()
+}
+
 // TODO: handle this as string instead of file
 pub fn update_constants_ts(
     server: &MCPServer,
@@ -57,7 +86,57 @@ pub fn update_constants_ts(
         "export const SERVER_VERSION = \"{}\";",
         server.version
     )?;
-    writeln!(code, "export const API_BASE_URL = \"{}\";", server.base_url)?;
+    let other_servers: Vec<_> = server
+        .servers
+        .iter()
+        .filter(|other| other.url != server.base_url)
+        .collect();
+    if !other_servers.is_empty() {
+        writeln!(code, "// Other servers of the API:")?;
+        for other in other_servers {
+            match &other.description {
+                Some(description) => writeln!(code, "// - {}: {}", other.url, description)?,
+                None => writeln!(code, "// - {}", other.url)?,
+            }
+        }
+    }
+
+    if !server.base_url_variables.is_empty() {
+        // Server variables can be overridden with `API_SERVER_<VARIABLE>` environment variables.
+        // `process` isn't defined in every runtime the template is built for, without it the
+        // defaults are used
+        writeln!(
+            code,
+            "const env: Record<string, string | undefined> = (globalThis as any).process?.env ?? {{}};"
+        )?;
+        writeln!(code, "export const API_SERVER_VARIABLES = {{")?;
+        for (name, variable) in &server.base_url_variables {
+            let env_name = format!("API_SERVER_{}", name.to_case(Case::Constant));
+            write!(
+                code,
+                "  \"{name}\": env.{env_name} ?? \"{}\",",
+                variable.default
+            )?;
+            let description = variable.description.as_ref().map(|d| d.replace('\n', " "));
+            match (&description, variable.values.is_empty()) {
+                (Some(description), true) => write!(code, " // {description}")?,
+                (Some(description), false) => write!(
+                    code,
+                    " // {description}, one of {}",
+                    variable.values.join(", ")
+                )?,
+                (None, false) => write!(code, " // One of {}", variable.values.join(", "))?,
+                (None, true) => {}
+            }
+            writeln!(code)?;
+        }
+        writeln!(code, "}};")?;
+    }
+    writeln!(
+        code,
+        "export const API_BASE_URL = {};",
+        server_url_code(&server.base_url, &server.base_url_variables)
+    )?;
 
     if let Some(oauth2_info) = &server.oauth2_info {
         writeln!(
@@ -75,3 +154,43 @@ pub fn update_constants_ts(
     std::fs::write(tools_index_path, &code)?;
     Ok(())
 }
+
+/// TypeScript expression of a server URL, its `{variable}` placeholders are read from
+/// `API_SERVER_VARIABLES` in `constants.ts`.
+pub(crate) fn server_url_code(url: &str, variables: &BTreeMap<String, ServerVariable>) -> String {
+    let placeholders: Vec<_> = variables
+        .keys()
+        .filter(|name| url.contains(&format!("{{{name}}}")))
+        .collect();
+    if placeholders.is_empty() {
+        return format!("\"{url}\"");
+    }
+    let mut url = url.replace('`', "\\`");
+    for name in placeholders {
+        url = url.replace(
+            &format!("{{{name}}}"),
+            &format!("${{API_SERVER_VARIABLES[\"{name}\"]}}"),
+        );
+    }
+    format!("`{url}`")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn module_names_are_valid_unique_identifiers() {
+        let names = module_names(["delete", "2faVerify", "get-users", "get_users", "get.users"]);
+        assert_eq!(
+            names,
+            [
+                "tool_delete",
+                "tool_2faVerify",
+                "tool_get_users",
+                "tool_get_users_2",
+                "tool_get_users_3"
+            ]
+        );
+    }
+}
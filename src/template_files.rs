@@ -40,7 +40,6 @@ fn parse_gitignore_line(line: &str) -> Option<(String, bool)> {
 pub fn get_all_files_in_dir_recursive(dir: &Path) -> anyhow::Result<Vec<PathBuf>> {
     fn visit_dir(
         dir: &Path,
-        root_dir: &Path,
         output: &mut Vec<PathBuf>,
         gitignore_patterns: &mut Vec<(PathBuf, Vec<(String, bool)>)>,
     ) -> anyhow::Result<()> {
@@ -51,13 +50,13 @@ pub fn get_all_files_in_dir_recursive(dir: &Path) -> anyhow::Result<Vec<PathBuf>
 
             // Check for .gitignore file in this directory
             let gitignore_path = dir.join(".gitignore");
-            if gitignore_path.exists() {
-                if let Ok(content) = fs::read_to_string(&gitignore_path) {
-                    let patterns: Vec<(String, bool)> =
-                        content.lines().filter_map(parse_gitignore_line).collect();
-                    if !patterns.is_empty() {
-                        gitignore_patterns.push((dir.to_path_buf(), patterns));
-                    }
+            if gitignore_path.exists()
+                && let Ok(content) = fs::read_to_string(&gitignore_path)
+            {
+                let patterns: Vec<(String, bool)> =
+                    content.lines().filter_map(parse_gitignore_line).collect();
+                if !patterns.is_empty() {
+                    gitignore_patterns.push((dir.to_path_buf(), patterns));
                 }
             }
 
@@ -71,7 +70,7 @@ pub fn get_all_files_in_dir_recursive(dir: &Path) -> anyhow::Result<Vec<PathBuf>
                 }
 
                 if path.is_dir() {
-                    visit_dir(&path, root_dir, output, gitignore_patterns)?;
+                    visit_dir(&path, output, gitignore_patterns)?;
                 } else {
                     output.push(path);
                 }
@@ -82,7 +81,7 @@ pub fn get_all_files_in_dir_recursive(dir: &Path) -> anyhow::Result<Vec<PathBuf>
 
     let mut output = Vec::new();
     let mut gitignore_patterns = Vec::new();
-    visit_dir(dir, dir, &mut output, &mut gitignore_patterns)?;
+    visit_dir(dir, &mut output, &mut gitignore_patterns)?;
     Ok(output)
 }
 
@@ -107,11 +106,10 @@ fn is_ignored(path: &Path, gitignore_patterns: &[(PathBuf, Vec<(String, bool)>)]
 
 fn matches_gitignore_pattern(path: &str, pattern: &str, is_dir: bool) -> bool {
     // Handle directory-only patterns (ending with /)
-    if pattern.ends_with('/') {
+    if let Some(pattern) = pattern.strip_suffix('/') {
         if !is_dir {
             return false;
         }
-        let pattern = &pattern[..pattern.len() - 1];
         return matches_gitignore_pattern(path, pattern, true);
     }
 
@@ -124,11 +122,7 @@ fn matches_gitignore_pattern(path: &str, pattern: &str, is_dir: bool) -> bool {
     }
 
     // Handle patterns starting with / (root-relative)
-    let pattern = if pattern.starts_with('/') {
-        &pattern[1..]
-    } else {
-        pattern
-    };
+    let pattern = pattern.strip_prefix('/').unwrap_or(pattern);
 
     // Simple wildcard matching
     if pattern.contains('*') {
@@ -136,10 +130,10 @@ fn matches_gitignore_pattern(path: &str, pattern: &str, is_dir: bool) -> bool {
         let regex_pattern = pattern.replace(".", "\\.").replace("*", ".*");
 
         // Use simple string matching for basic cases
-        if let Ok(re) = regex::Regex::new(&format!("^{}$", regex_pattern)) {
-            if re.is_match(path) {
-                return true;
-            }
+        if let Ok(re) = regex::Regex::new(&format!("^{}$", regex_pattern))
+            && re.is_match(path)
+        {
+            return true;
         }
     }
 
@@ -50,6 +50,108 @@ mod tests {
         test_generate("adobe-firefly", Default::default());
     }
 
+    #[test]
+    fn zoom() {
+        test_generate("zoom", Default::default());
+    }
+
+    #[test]
+    fn multi_file() {
+        test_generate("multi-file", Default::default());
+
+        // `/owners` is a path item `$ref` to another file
+        let tools_dir = "./tests/multi-file/generated/src/routes/v1/mcp/tools";
+        let list_owners = fs::read_to_string(format!("{tools_dir}/get_owners.ts")).unwrap();
+        assert!(list_owners.contains("List the pet owners"));
+        assert!(list_owners.contains("city: z.string()"));
+    }
+
+    #[test]
+    fn openapi_31() {
+        test_generate("openapi-31", Default::default());
+
+        let tools_dir = "./tests/openapi-31/generated/src/routes/v1/mcp/tools";
+        let list_pets = fs::read_to_string(format!("{tools_dir}/get_pets.ts")).unwrap();
+        // `type: [integer, 'null']` with a numeric `exclusiveMinimum`
+        assert!(list_pets.contains("limit: z.number().int().gt(0).nullable().optional()"));
+        let create_pet = fs::read_to_string(format!("{tools_dir}/post_pets.ts")).unwrap();
+        // `const`
+        assert!(create_pet.contains("\"kind\": z.literal(\"dog\")"));
+        // `$defs` reference with `type: [string, 'null']`
+        assert!(create_pet.contains("\"tag\": z.string().nullable().optional()"));
+        // `oneOf` with a `null` variant
+        assert!(create_pet.contains("\"name\": z.string().optional(),\n      }).nullable()"));
+        assert!(!create_pet.contains("z.any()"));
+    }
+
+    #[test]
+    fn swagger_2() {
+        test_generate("swagger-2", Default::default());
+
+        let project_path = "./tests/swagger-2/generated/src";
+        let constants = fs::read_to_string(format!("{project_path}/constants.ts")).unwrap();
+        // `schemes`, `host` and `basePath`
+        assert!(constants.contains("API_BASE_URL = \"https://files.example.com/api\""));
+
+        let tools_dir = format!("{project_path}/routes/v1/mcp/tools");
+        let upload = fs::read_to_string(format!("{tools_dir}/post_files.ts")).unwrap();
+        assert!(upload.contains("body: toFormData(args.body, [\"content\"])"));
+        let rename = fs::read_to_string(format!("{tools_dir}/patch_files_id.ts")).unwrap();
+        assert!(rename.contains("\"Content-Type\": \"application/x-www-form-urlencoded\""));
+        assert!(rename.contains("body: toUrlSearchParams(args.body)"));
+    }
+
+    #[test]
+    fn servers() {
+        test_generate("servers", Default::default());
+
+        let project_path = "./tests/servers/generated/src";
+        let constants = fs::read_to_string(format!("{project_path}/constants.ts")).unwrap();
+        assert!(constants.contains("(globalThis as any).process?.env ?? {}"));
+        assert!(constants.contains("\"bucket\": env.API_SERVER_BUCKET ?? \"uploads\""));
+        assert!(constants.contains(
+            "API_BASE_URL = `https://${API_SERVER_VARIABLES[\"region\"]}.storage.example.com/v1`"
+        ));
+
+        let tools_dir = format!("{project_path}/routes/v1/mcp/tools");
+        let upload =
+            fs::read_to_string(format!("{tools_dir}/post_buckets_bucket_objects.ts")).unwrap();
+        assert!(upload.contains(
+            "callServer(`https://${API_SERVER_VARIABLES[\"bucket\"]}.${API_SERVER_VARIABLES[\"region\"]}.files.example.com`, {"
+        ));
+        assert!(!upload.contains("httpClient"));
+        let list = fs::read_to_string(format!("{tools_dir}/get_buckets.ts")).unwrap();
+        assert!(list.contains("httpClient.call({"));
+    }
+
+    #[test]
+    fn parameter_styles() {
+        test_generate("parameter-styles", Default::default());
+
+        let tools_dir = "./tests/parameter-styles/generated/src/routes/v1/mcp/tools";
+        let list_issues = fs::read_to_string(format!("{tools_dir}/get_issues.ts")).unwrap();
+        // a free-form deepObject keeps its keys, so filter[status]=open is sent
+        assert!(list_issues.contains("filter: z.record("));
+        assert!(list_issues.contains("[\"filter\", args.filter, \"deepObject\", true]"));
+    }
+
+    #[test]
+    fn overlay() {
+        test_generate(
+            "overlay",
+            GenerateOptions {
+                overlays: vec!["./tests/overlay/overlay.yaml".into()],
+                ..Default::default()
+            },
+        );
+
+        let tools_dir = "./tests/overlay/generated/src/routes/v1/mcp/tools";
+        let list_pets = fs::read_to_string(format!("{tools_dir}/get_pets.ts")).unwrap();
+        assert!(list_pets.contains("Lists the pets in the store"));
+        assert!(!list_pets.contains("X-Internal-Trace"));
+        assert!(!Path::new(&format!("{tools_dir}/get_internal_metrics.ts")).exists());
+    }
+
     #[test]
     fn microsoft_graph() {
         test_generate(